@@ -0,0 +1,12 @@
+CREATE TABLE leaderboard_snapshots (
+    id              VARCHAR(255) NOT NULL PRIMARY KEY,
+    competition_id  VARCHAR(255) NOT NULL,
+    round           INTEGER NOT NULL,
+    team_id         VARCHAR(255) NOT NULL,
+    team_rank       INTEGER NOT NULL,
+    elo             INTEGER NOT NULL,
+    wins            INTEGER NOT NULL,
+    losses          INTEGER NOT NULL,
+    games_played    INTEGER NOT NULL,
+    created         DATETIME NOT NULL
+);
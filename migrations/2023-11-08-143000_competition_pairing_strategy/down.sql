@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE competitions DROP COLUMN pairing_strategy;
\ No newline at end of file
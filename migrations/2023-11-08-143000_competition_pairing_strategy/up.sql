@@ -0,0 +1 @@
+ALTER TABLE competitions ADD COLUMN pairing_strategy VARCHAR(255) NOT NULL DEFAULT 'random';
@@ -0,0 +1 @@
+ALTER TABLE competitions ADD COLUMN pairing_params VARCHAR(1024) NOT NULL DEFAULT '{}';
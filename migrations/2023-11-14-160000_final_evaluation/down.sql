@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE final_evaluation_standings;
+DROP TABLE final_evaluations;
+ALTER TABLE games_2v2 DROP COLUMN evaluation_id;
\ No newline at end of file
@@ -0,0 +1,20 @@
+ALTER TABLE games_2v2 ADD COLUMN evaluation_id VARCHAR(255) NOT NULL DEFAULT '';
+
+CREATE TABLE final_evaluations (
+    id              VARCHAR(255) NOT NULL PRIMARY KEY,
+    competition_id  VARCHAR(255) NOT NULL,
+    games_per_pair  INTEGER NOT NULL,
+    status          VARCHAR(255) NOT NULL,
+    created         DATETIME NOT NULL
+);
+
+CREATE TABLE final_evaluation_standings (
+    id              VARCHAR(255) NOT NULL PRIMARY KEY,
+    evaluation_id   VARCHAR(255) NOT NULL,
+    team_id         VARCHAR(255) NOT NULL,
+    team_rank       INTEGER NOT NULL,
+    wins            INTEGER NOT NULL,
+    losses          INTEGER NOT NULL,
+    games_played    INTEGER NOT NULL,
+    created         DATETIME NOT NULL
+);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE bracket_matches;
+DROP TABLE brackets;
\ No newline at end of file
@@ -0,0 +1,30 @@
+CREATE TABLE brackets (
+    id              VARCHAR(255) NOT NULL PRIMARY KEY,
+    competition_id  VARCHAR(255) NOT NULL,
+    elimination     VARCHAR(255) NOT NULL,
+    size            INTEGER NOT NULL,
+    best_of         INTEGER NOT NULL,
+    status          VARCHAR(255) NOT NULL,
+    created         DATETIME NOT NULL
+);
+
+CREATE TABLE bracket_matches (
+    id              VARCHAR(255) NOT NULL PRIMARY KEY,
+    bracket_id      VARCHAR(255) NOT NULL,
+    stage           VARCHAR(255) NOT NULL,
+    round           INTEGER NOT NULL,
+    position        INTEGER NOT NULL,
+    team1_id        VARCHAR(255) NOT NULL,
+    team2_id        VARCHAR(255) NOT NULL,
+    team1_wins      INTEGER NOT NULL,
+    team2_wins      INTEGER NOT NULL,
+    winner_id       VARCHAR(255) NOT NULL,
+    loser_id        VARCHAR(255) NOT NULL,
+    winner_to       VARCHAR(255) NOT NULL,
+    winner_slot     INTEGER NOT NULL,
+    loser_to        VARCHAR(255) NOT NULL,
+    loser_slot      INTEGER NOT NULL,
+    games           TEXT NOT NULL,
+    status          VARCHAR(255) NOT NULL,
+    created         DATETIME NOT NULL
+);
@@ -0,0 +1 @@
+ALTER TABLE competitions ADD COLUMN schedule VARCHAR(255) NOT NULL DEFAULT '0 0 * * * * *';
\ No newline at end of file
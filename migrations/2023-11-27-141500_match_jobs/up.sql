@@ -0,0 +1,15 @@
+CREATE TABLE match_jobs (
+    id              VARCHAR(255) NOT NULL PRIMARY KEY,
+    competition_id  VARCHAR(255) NOT NULL,
+    round           INTEGER NOT NULL,
+    team1_id        VARCHAR(255) NOT NULL,
+    team2_id        VARCHAR(255) NOT NULL,
+    team1bot1_id    VARCHAR(255) NOT NULL,
+    team1bot2_id    VARCHAR(255) NOT NULL,
+    team2bot1_id    VARCHAR(255) NOT NULL,
+    team2bot2_id    VARCHAR(255) NOT NULL,
+    status          VARCHAR(255) NOT NULL,
+    error           TEXT NOT NULL,
+    created         DATETIME NOT NULL,
+    updated         DATETIME NOT NULL
+);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE match_jobs DROP COLUMN worker_id;
+
+DROP TABLE workers;
\ No newline at end of file
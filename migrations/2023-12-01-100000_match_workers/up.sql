@@ -0,0 +1,8 @@
+CREATE TABLE workers (
+    id              VARCHAR(255) NOT NULL PRIMARY KEY,
+    name            VARCHAR(255) NOT NULL,
+    last_seen       DATETIME NOT NULL,
+    created         DATETIME NOT NULL
+);
+
+ALTER TABLE match_jobs ADD COLUMN worker_id VARCHAR(255) NOT NULL DEFAULT '';
@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE competitions DROP COLUMN sandbox_limits;
\ No newline at end of file
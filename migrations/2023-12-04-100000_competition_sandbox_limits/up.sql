@@ -0,0 +1 @@
+ALTER TABLE competitions ADD COLUMN sandbox_limits VARCHAR(1024) NOT NULL DEFAULT '{}';
\ No newline at end of file
@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE games_2v2 DROP COLUMN leftover_processes;
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE games_2v2 ADD COLUMN leftover_processes INTEGER NOT NULL DEFAULT 0;
\ No newline at end of file
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE games_2v2 DROP COLUMN outcome;
+ALTER TABLE competitions DROP COLUMN timeout_policy;
+ALTER TABLE competitions DROP COLUMN match_timeout;
\ No newline at end of file
@@ -0,0 +1,3 @@
+ALTER TABLE competitions ADD COLUMN match_timeout INTEGER NOT NULL DEFAULT 120;
+ALTER TABLE competitions ADD COLUMN timeout_policy VARCHAR(255) NOT NULL DEFAULT 'score';
+ALTER TABLE games_2v2 ADD COLUMN outcome VARCHAR(255) NOT NULL DEFAULT 'finished';
\ No newline at end of file
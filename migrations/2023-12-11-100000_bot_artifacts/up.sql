@@ -0,0 +1,2 @@
+ALTER TABLE bots ADD COLUMN source_hash VARCHAR(64) NOT NULL DEFAULT '';
+ALTER TABLE bots ADD COLUMN compile_status VARCHAR(255) NOT NULL DEFAULT 'pending';
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE competitions ADD COLUMN language VARCHAR(255) NOT NULL DEFAULT '';
\ No newline at end of file
@@ -0,0 +1,2 @@
+ALTER TABLE bots ADD COLUMN compile_diagnostics TEXT NOT NULL;
+UPDATE bots SET compile_diagnostics = '[]';
\ No newline at end of file
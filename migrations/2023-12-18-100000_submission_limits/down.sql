@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE competitions DROP COLUMN max_upload_mb;
+ALTER TABLE competitions DROP COLUMN upload_quota;
\ No newline at end of file
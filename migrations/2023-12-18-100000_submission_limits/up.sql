@@ -0,0 +1,2 @@
+ALTER TABLE competitions ADD COLUMN upload_quota INTEGER NOT NULL DEFAULT 20;
+ALTER TABLE competitions ADD COLUMN max_upload_mb INTEGER NOT NULL DEFAULT 10;
\ No newline at end of file
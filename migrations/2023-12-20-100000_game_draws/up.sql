@@ -0,0 +1,2 @@
+ALTER TABLE games_2v2 ADD COLUMN draw BOOLEAN NOT NULL DEFAULT FALSE;
+UPDATE games_2v2 SET draw = TRUE WHERE winner_id = '';
\ No newline at end of file
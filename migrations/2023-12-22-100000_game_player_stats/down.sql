@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE game_player_stats;
\ No newline at end of file
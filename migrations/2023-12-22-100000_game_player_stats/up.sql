@@ -0,0 +1,28 @@
+CREATE TABLE game_player_stats (
+    game_id                 VARCHAR(255) NOT NULL,
+    player                  VARCHAR(255) NOT NULL,
+    bot_id                  VARCHAR(255) NOT NULL,
+    team_id                 VARCHAR(255) NOT NULL,
+    competition_id          VARCHAR(255) NOT NULL,
+    round                   INTEGER NOT NULL,
+    turns_played            INTEGER NOT NULL,
+    survived                BOOLEAN NOT NULL,
+    fleet_generated         INTEGER NOT NULL,
+    fleet_lost              INTEGER NOT NULL,
+    fleet_reinforced        INTEGER NOT NULL,
+    largest_attack          INTEGER NOT NULL,
+    largest_loss            INTEGER NOT NULL,
+    largest_reinforcement   INTEGER NOT NULL,
+    planets_lost            INTEGER NOT NULL,
+    planets_conquered       INTEGER NOT NULL,
+    planets_defended        INTEGER NOT NULL,
+    planets_attacked        INTEGER NOT NULL,
+    num_fleet_lost          INTEGER NOT NULL,
+    num_fleet_reinforced    INTEGER NOT NULL,
+    num_fleet_generated     INTEGER NOT NULL,
+    total_troops_generated  INTEGER NOT NULL,
+    PRIMARY KEY (game_id, player)
+);
+
+CREATE INDEX game_player_stats_bot_id ON game_player_stats (bot_id);
+CREATE INDEX game_player_stats_team_id ON game_player_stats (team_id);
\ No newline at end of file
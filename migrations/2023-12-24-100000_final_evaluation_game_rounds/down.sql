@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+-- The game numbers stored as rounds before cannot be restored, they carried no information.
+SELECT 1;
\ No newline at end of file
@@ -0,0 +1,2 @@
+UPDATE games_2v2 SET round = 0 WHERE evaluation_id IN (SELECT id FROM final_evaluations);
+UPDATE game_player_stats SET round = 0 WHERE game_id IN (SELECT id FROM games_2v2 WHERE evaluation_id IN (SELECT id FROM final_evaluations));
\ No newline at end of file
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+UPDATE games_2v2 SET evaluation_id = bracket_id WHERE bracket_id <> '';
+ALTER TABLE games_2v2 DROP COLUMN bracket_id;
\ No newline at end of file
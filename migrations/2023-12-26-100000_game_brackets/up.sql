@@ -0,0 +1,2 @@
+ALTER TABLE games_2v2 ADD COLUMN bracket_id VARCHAR(255) NOT NULL DEFAULT '';
+UPDATE games_2v2 SET bracket_id = evaluation_id, evaluation_id = '' WHERE evaluation_id IN (SELECT id FROM brackets);
\ No newline at end of file
@@ -0,0 +1,2 @@
+ALTER TABLE games_2v2 ADD COLUMN void BOOLEAN NOT NULL DEFAULT FALSE;
+UPDATE games_2v2 SET void = TRUE, draw = FALSE WHERE outcome <> 'finished' AND winner_id = '' AND competition_id IN (SELECT id FROM competitions WHERE timeout_policy = 'void');
\ No newline at end of file
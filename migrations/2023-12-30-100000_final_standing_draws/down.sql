@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE final_evaluation_standings DROP COLUMN draws;
\ No newline at end of file
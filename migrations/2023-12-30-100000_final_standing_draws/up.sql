@@ -0,0 +1 @@
+ALTER TABLE final_evaluation_standings ADD COLUMN draws INTEGER NOT NULL DEFAULT 0;
\ No newline at end of file
@@ -0,0 +1,2 @@
+ALTER TABLE bots ADD COLUMN compile_language VARCHAR(255) NOT NULL DEFAULT '';
+UPDATE bots JOIN teams ON bots.team_id = teams.id JOIN competitions ON teams.competition_id = competitions.id SET bots.compile_language = competitions.language;
\ No newline at end of file
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+-- The removed compiler output cannot be restored, the bots are compiled again instead.
+SELECT 1;
\ No newline at end of file
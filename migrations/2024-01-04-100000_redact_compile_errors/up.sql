@@ -0,0 +1 @@
+UPDATE bots SET compile_status = 'pending', compile_error = '', compile_diagnostics = '[]' WHERE compile_status = 'failed';
\ No newline at end of file
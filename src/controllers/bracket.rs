@@ -0,0 +1,408 @@
+use std::{cmp::Reverse, collections::HashMap, thread};
+
+use crate::{
+    db::{
+        operations_competition::get_competition_by_id,
+        operations_teams::get_teams_by_competition_id,
+        operations_bracket::{
+            insert_bracket, insert_bracket_matches, get_bracket_matches, get_brackets_by_status,
+            update_bracket_match, set_bracket_match_team, set_bracket_status
+        }
+    },
+    models::{
+        errors::MatchMakerError,
+        bracket::{Bracket, NewBracket, BracketMatch, NewBracketMatch},
+        competition::Competition,
+        game_2v2::NewGame2v2,
+        team::Team
+    }
+};
+
+use super::matchmaker_2v2::{compile_team_bots, run_matches};
+
+/// The number of games without a winner (draws, void or failed games) a series may have before
+/// the bracket is given up.
+const MAX_UNDECIDED_GAMES: usize = 3;
+
+/// Starts a knockout bracket for the best teams of a competition.
+///
+/// The bots of all teams are compiled first, and the `team_count` teams with the highest ELO
+/// among those that compiled are seeded into the bracket (the best team is seed 1). The bracket
+/// size is rounded up to the next power of two, the missing slots are byes given to the top seeds.
+/// Double elimination needs at least four slots, smaller brackets are played as single elimination.
+/// If the team coming from the losers bracket wins the grand final, both teams have lost one
+/// series and the final is reset: it is played once more to decide the bracket.
+///
+/// The bracket is stored right away with the `running` status, while its matches are played
+/// on a separate thread. Its status is set to `done` once the final is decided (or `failed` if
+/// the bracket could not be completed). Every decided match and every game of a series is
+/// stored as soon as it is played, so a bracket interrupted by a restart is picked up again
+/// where it stopped, see `resume_brackets`.
+///
+/// # Arguments
+///
+/// * `competition_id` - The ID of the competition the bracket is played for.
+/// * `team_count` - The number of teams to seed into the bracket.
+/// * `best_of` - The number of games of a series, a team needs a majority of them to advance.
+/// * `double_elimination` - Whether teams get a second chance in a losers bracket.
+///
+/// # Returns
+///
+/// A `Result` containing the started `Bracket`, or a `MatchMakerError` if the competition does
+/// not exist, fewer than two teams can be seeded or the bracket could not be stored.
+///
+pub fn start_bracket(competition_id: String, team_count: usize, best_of: i32, double_elimination: bool) -> Result<Bracket, MatchMakerError> {
+    let competition = match get_competition_by_id(competition_id) {
+        Ok(c) => c,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+
+    let teams = match get_teams_by_competition_id(competition.id.clone()) {
+        Ok(teams) => teams,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+
+    let mut seeds = compile_team_bots(teams, &competition.language);
+    seeds.sort_by_key(|team| Reverse(team.elo));
+    seeds.truncate(team_count);
+    if seeds.len() < 2 {
+        return Err(MatchMakerError::NotEnoughTeams);
+    }
+
+    let size = seeds.len().next_power_of_two();
+    let double_elimination = double_elimination && size >= 4;
+    let bracket = match insert_bracket(NewBracket {
+        competition_id: competition.id.clone(),
+        elimination: if double_elimination { "double" } else { "single" }.to_string(),
+        size: size as i32,
+        best_of,
+    }) {
+        Ok(b) => b,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+
+    let seed_ids: Vec<String> = seeds.iter().map(|team| team.id.clone()).collect();
+    if let Err(e) = insert_bracket_matches(create_bracket_matches(&bracket.id, &seed_ids, size, double_elimination)) {
+        return Err(MatchMakerError::DatabaseError(e));
+    }
+
+    spawn_bracket(competition, bracket.clone(), seeds);
+    Ok(bracket)
+}
+
+/// Resumes the brackets that were interrupted, e.g. because the process died while their
+/// matches were played.
+///
+/// Every bracket still `running` continues with the matches that are not decided yet, on a
+/// separate thread. The bots of the competition's teams are compiled again, a team whose bots
+/// don't compile anymore can't play its series and the bracket fails.
+///
+/// # Errors
+///
+/// Returns a `MatchMakerError` if the running brackets cannot be fetched. Brackets whose
+/// competition or teams cannot be fetched are logged and do not stop the others from resuming.
+///
+pub fn resume_brackets() -> Result<(), MatchMakerError> {
+    let brackets = match get_brackets_by_status("running".to_string()) {
+        Ok(brackets) => brackets,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+    for bracket in brackets.into_iter() {
+        println!("Resuming bracket {} of competition {}", bracket.id, bracket.competition_id);
+        let competition = match get_competition_by_id(bracket.competition_id.clone()) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Error on resuming bracket {}: {:?}", bracket.id, e);
+                continue;
+            }
+        };
+        let teams = match get_teams_by_competition_id(competition.id.clone()) {
+            Ok(teams) => teams,
+            Err(e) => {
+                eprintln!("Error on resuming bracket {}: {:?}", bracket.id, e);
+                continue;
+            }
+        };
+        let compiled_teams = compile_team_bots(teams, &competition.language);
+        spawn_bracket(competition, bracket, compiled_teams);
+    }
+    Ok(())
+}
+
+fn spawn_bracket(competition: Competition, bracket: Bracket, teams: Vec<Team>) {
+    thread::spawn(move || {
+        let status = match run_bracket(&competition, &bracket, teams) {
+            Ok(_) => "done",
+            Err(e) => {
+                eprintln!("Error on running bracket {}: {:?}", bracket.id, e);
+                "failed"
+            }
+        };
+        if let Err(e) = set_bracket_status(bracket.id.clone(), status.to_string()) {
+            eprintln!("Error on setting bracket status: {:?}", e);
+        }
+    });
+}
+
+/// Builds all matches of a bracket and links them together.
+///
+/// Every match points to the matches its winner and its loser move on to (`winner_to` and
+/// `loser_to`, with the slot they take there). Only the first winners round gets its teams now,
+/// an empty team ID stands for a bye.
+fn create_bracket_matches(bracket_id: &str, seeds: &[String], size: usize, double_elimination: bool) -> Vec<NewBracketMatch> {
+    let winners_rounds = size.trailing_zeros() as usize;
+    let new_match = |stage: &str, round: usize, position: usize| {
+        NewBracketMatch::new(bracket_id.to_string(), stage, round as i32, position as i32)
+    };
+
+    let mut winners: Vec<Vec<NewBracketMatch>> = (1..=winners_rounds)
+        .map(|round| (0..size >> round).map(|position| new_match("winners", round, position)).collect())
+        .collect();
+
+    let order = seed_order(size);
+    for (position, bracket_match) in winners[0].iter_mut().enumerate() {
+        let seed_team = |seed: usize| seeds.get(seed - 1).cloned().unwrap_or_default();
+        bracket_match.team1_id = seed_team(order[2 * position]);
+        bracket_match.team2_id = seed_team(order[2 * position + 1]);
+    }
+
+    for round in 0..winners_rounds - 1 {
+        for position in 0..winners[round].len() {
+            let next_id = winners[round + 1][position / 2].id.clone();
+            link_winner(&mut winners[round][position], next_id, position);
+        }
+    }
+
+    if !double_elimination {
+        return winners.into_iter().flatten().collect();
+    }
+
+    // the losers bracket alternates between rounds where its survivors meet the losers
+    // dropping down from the winners bracket, and rounds where the survivors play each other
+    let losers_rounds = 2 * (winners_rounds - 1);
+    let mut losers: Vec<Vec<NewBracketMatch>> = (1..=losers_rounds)
+        .map(|round| (0..size >> (round.div_ceil(2) + 1)).map(|position| new_match("losers", round, position)).collect())
+        .collect();
+
+    for position in 0..winners[0].len() {
+        let target = &losers[0][position / 2];
+        winners[0][position].loser_to = target.id.clone();
+        winners[0][position].loser_slot = position as i32 % 2 + 1;
+    }
+    for round in 1..winners_rounds {
+        // the losers drop into the bracket in reversed order, so teams that met in the
+        // winners bracket don't meet again right away
+        let target_round = &losers[2 * round - 1];
+        let count = target_round.len();
+        let targets: Vec<String> = target_round.iter().map(|m| m.id.clone()).collect();
+        for (position, bracket_match) in winners[round].iter_mut().enumerate() {
+            bracket_match.loser_to = targets[count - 1 - position].clone();
+            bracket_match.loser_slot = 2;
+        }
+    }
+
+    for round in 0..losers_rounds - 1 {
+        for position in 0..losers[round].len() {
+            // odd rounds (counted from one) feed the same position of the next round
+            if round % 2 == 0 {
+                losers[round][position].winner_to = losers[round + 1][position].id.clone();
+                losers[round][position].winner_slot = 1;
+            } else {
+                let next_id = losers[round + 1][position / 2].id.clone();
+                link_winner(&mut losers[round][position], next_id, position);
+            }
+        }
+    }
+
+    let mut grand_final = new_match("final", 1, 0);
+    let reset = new_match("final", 2, 0);
+    grand_final.winner_to = reset.id.clone();
+    grand_final.winner_slot = 1;
+    grand_final.loser_to = reset.id.clone();
+    grand_final.loser_slot = 2;
+    let winners_final = &mut winners[winners_rounds - 1][0];
+    winners_final.winner_to = grand_final.id.clone();
+    winners_final.winner_slot = 1;
+    let losers_final = &mut losers[losers_rounds - 1][0];
+    losers_final.winner_to = grand_final.id.clone();
+    losers_final.winner_slot = 2;
+
+    let mut matches: Vec<NewBracketMatch> = winners.into_iter().flatten().collect();
+    matches.extend(losers.into_iter().flatten());
+    matches.push(grand_final);
+    matches.push(reset);
+    matches
+}
+
+fn link_winner(bracket_match: &mut NewBracketMatch, next_id: String, position: usize) {
+    bracket_match.winner_to = next_id;
+    bracket_match.winner_slot = position as i32 % 2 + 1;
+}
+
+/// Returns the seeds in bracket order, so that the two best seeds can only meet in the final,
+/// the four best seeds only in the semifinals and so on (e.g. `1, 8, 4, 5, 2, 7, 3, 6` for 8 slots).
+fn seed_order(size: usize) -> Vec<usize> {
+    let mut order = vec![1];
+    while order.len() < size {
+        let next = order.len() * 2;
+        order = order.iter().flat_map(|seed| [*seed, next + 1 - seed]).collect();
+    }
+    order
+}
+
+/// Plays the matches of a bracket until all of them are decided.
+///
+/// Matches are played in waves: every match whose feeding matches are all decided plays its
+/// next game, and all games of a wave run in parallel. Byes are decided without playing.
+/// Games without a winner don't count towards a series and are replayed, up to
+/// `MAX_UNDECIDED_GAMES` times per series (counting the stored games without a winner of a
+/// resumed series).
+fn run_bracket(competition: &Competition, bracket: &Bracket, teams: Vec<Team>) -> Result<(), MatchMakerError> {
+    println!("Running bracket: {}", bracket.id);
+    let teams: HashMap<String, Team> = teams
+        .into_iter()
+        .map(|team| (team.id.clone(), team))
+        .collect();
+    let wins_needed = bracket.best_of / 2 + 1;
+    let mut undecided_games: HashMap<String, usize> = HashMap::new();
+
+    loop {
+        let matches = match get_bracket_matches(bracket.id.clone()) {
+            Ok(matches) => matches,
+            Err(e) => return Err(MatchMakerError::DatabaseError(e))
+        };
+        if matches.iter().all(|m| m.status.eq("done")) {
+            break;
+        }
+
+        let ready: Vec<BracketMatch> = matches
+            .iter()
+            .filter(|m| !m.status.eq("done"))
+            .filter(|m| matches
+                .iter()
+                .filter(|feeder| feeder.winner_to.eq(&m.id) || feeder.loser_to.eq(&m.id))
+                .all(|feeder| feeder.status.eq("done")))
+            .cloned()
+            .collect();
+        if ready.is_empty() {
+            return Err(MatchMakerError::GameProcessFailed);
+        }
+
+        let (playable, byes): (Vec<BracketMatch>, Vec<BracketMatch>) = ready
+            .into_iter()
+            .partition(|m| !m.team1_id.is_empty() && !m.team2_id.is_empty());
+
+        for mut bye in byes.into_iter() {
+            let winner = if bye.team1_id.is_empty() { bye.team2_id.clone() } else { bye.team1_id.clone() };
+            decide_match(&mut bye, winner, "".to_string())?;
+        }
+        if playable.is_empty() {
+            continue;
+        }
+
+        let games = playable
+            .iter()
+            .filter_map(|m| create_series_game(bracket, m, &teams))
+            .collect();
+        let played = run_matches(competition, games);
+
+        for mut bracket_match in playable.into_iter() {
+            let mut game_ids: Vec<String> = serde_json::from_str(&bracket_match.games).unwrap_or_default();
+            let stored_undecided = game_ids.len().saturating_sub((bracket_match.team1_wins + bracket_match.team2_wins) as usize);
+            let mut decided = false;
+            for game in played.iter().filter(|g| {
+                (g.team1_id.eq(&bracket_match.team1_id) && g.team2_id.eq(&bracket_match.team2_id))
+                    || (g.team1_id.eq(&bracket_match.team2_id) && g.team2_id.eq(&bracket_match.team1_id))
+            }) {
+                game_ids.push(game.id.clone());
+                if game.winner_id.eq(&bracket_match.team1_id) {
+                    bracket_match.team1_wins += 1;
+                    decided = true;
+                } else if game.winner_id.eq(&bracket_match.team2_id) {
+                    bracket_match.team2_wins += 1;
+                    decided = true;
+                }
+            }
+            bracket_match.games = serde_json::to_string(&game_ids).unwrap_or("[]".to_string());
+
+            if bracket_match.team1_wins >= wins_needed {
+                let (winner, loser) = (bracket_match.team1_id.clone(), bracket_match.team2_id.clone());
+                decide_match(&mut bracket_match, winner, loser)?;
+                if bracket_match.stage.eq("final") {
+                    // the team from the winners bracket has not lost a series yet
+                    skip_bracket_reset(&matches, &bracket_match)?;
+                }
+            } else if bracket_match.team2_wins >= wins_needed {
+                let (winner, loser) = (bracket_match.team2_id.clone(), bracket_match.team1_id.clone());
+                decide_match(&mut bracket_match, winner, loser)?;
+            } else {
+                bracket_match.status = "running".to_string();
+                if let Err(e) = update_bracket_match(&bracket_match) {
+                    return Err(MatchMakerError::DatabaseError(e));
+                }
+            }
+
+            if !decided {
+                let undecided = undecided_games.entry(bracket_match.id.clone()).or_insert(stored_undecided);
+                *undecided += 1;
+                if *undecided > MAX_UNDECIDED_GAMES {
+                    return Err(MatchMakerError::UndecidedMatch(format!(
+                        "Match {} of bracket {} had {} games without a winner",
+                        bracket_match.id, bracket.id, undecided
+                    )));
+                }
+            }
+        }
+    }
+    println!("Bracket done!");
+    Ok(())
+}
+
+/// Creates the next game of a series, the teams switch sides after every game played
+/// (including the games without a winner).
+fn create_series_game(bracket: &Bracket, bracket_match: &BracketMatch, teams: &HashMap<String, Team>) -> Option<NewGame2v2> {
+    let team1 = teams.get(&bracket_match.team1_id)?;
+    let team2 = teams.get(&bracket_match.team2_id)?;
+    let played = serde_json::from_str::<Vec<String>>(&bracket_match.games).unwrap_or_default().len();
+    let (team1, team2) = if played % 2 == 1 { (team2, team1) } else { (team1, team2) };
+
+    let mut match_game = NewGame2v2::from_teams(bracket.competition_id.clone(), bracket_match.round, team1, team2);
+    match_game.bracket_id = bracket.id.clone();
+    Some(match_game)
+}
+
+/// Decides the reset of the grand final without playing it, after the team coming from the
+/// winners bracket won the grand final. Brackets without a reset are left as they are.
+fn skip_bracket_reset(matches: &[BracketMatch], grand_final: &BracketMatch) -> Result<(), MatchMakerError> {
+    let mut reset = match matches.iter().find(|m| m.id.eq(&grand_final.winner_to)) {
+        Some(reset) => reset.clone(),
+        None => return Ok(())
+    };
+    reset.team1_id = grand_final.winner_id.clone();
+    reset.team2_id = grand_final.loser_id.clone();
+    decide_match(&mut reset, grand_final.winner_id.clone(), grand_final.loser_id.clone())
+}
+
+/// Stores the result of a match and moves the winner and the loser to their next matches.
+fn decide_match(bracket_match: &mut BracketMatch, winner: String, loser: String) -> Result<(), MatchMakerError> {
+    bracket_match.winner_id = winner.clone();
+    bracket_match.loser_id = loser.clone();
+    bracket_match.status = "done".to_string();
+    if let Err(e) = update_bracket_match(bracket_match) {
+        return Err(MatchMakerError::DatabaseError(e));
+    }
+
+    let moves = [
+        (&bracket_match.winner_to, bracket_match.winner_slot, winner),
+        (&bracket_match.loser_to, bracket_match.loser_slot, loser),
+    ];
+    for (target, slot, team_id) in moves.into_iter() {
+        if target.is_empty() {
+            continue;
+        }
+        if let Err(e) = set_bracket_match_team(target.clone(), slot, team_id) {
+            return Err(MatchMakerError::DatabaseError(e));
+        }
+    }
+    Ok(())
+}
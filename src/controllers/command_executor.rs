@@ -1,28 +1,58 @@
 use std::fs;
-use std::io::{Error, ErrorKind, BufReader, BufRead};
+use std::io::{self, Error, ErrorKind, BufReader, BufRead, Read};
 use std::path::Path;
 use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
 
+use wait_timeout::ChildExt;
 
-pub fn execute_command(command: String, args: Vec<&str>) -> std::io::Result<Vec<String>> {
-    let mut child = Command::new(command)
-        .args(&args)
+/// The most a command may write to stderr that is kept, the rest is discarded.
+const MAX_ERROR_OUTPUT: u64 = 64 * 1024;
+
+/// Runs a command and returns the lines it wrote to stdout.
+///
+/// If the command fails, the returned error holds what it wrote to stderr (at most
+/// `MAX_ERROR_OUTPUT` bytes), e.g. the messages of a compiler. A command running longer than
+/// `timeout` is killed and fails with `ErrorKind::TimedOut`.
+pub fn execute_command(mut command: Command, timeout: Duration) -> std::io::Result<Vec<String>> {
+    let mut child = command
         .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
         .spawn()?;
 
-    let stdout = child.stdout.take().ok_or_else(|| {
-        Error::new(ErrorKind::Other, "Could not capture standard output.")
-    })?;
-    
-    let reader = BufReader::new(stdout);
-    let output_lines: Vec<String> = reader.lines().collect::<Result<_, _>>()?;
+    let stdout = child.stdout.take().ok_or_else(|| Error::other("Could not capture standard output."))?;
+    let stderr = child.stderr.take().ok_or_else(|| Error::other("Could not capture standard error."))?;
+
+    // stderr is read on its own thread, the command blocks if either pipe fills up
+    let stderr_handle = thread::spawn(move || {
+        let mut reader = BufReader::new(stderr);
+        let mut errors = Vec::new();
+        let _ = (&mut reader).take(MAX_ERROR_OUTPUT).read_to_end(&mut errors);
+        let _ = io::copy(&mut reader, &mut io::sink());
+        String::from_utf8_lossy(&errors).to_string()
+    });
+    let stdout_handle = thread::spawn(move || {
+        BufReader::new(stdout).lines().collect::<Result<Vec<String>, Error>>()
+    });
 
     // Wait for the process to finish and check the exit status
-    let status = child.wait()?;
+    let status = match child.wait_timeout(timeout)? {
+        Some(status) => status,
+        None => {
+            child.kill()?;
+            child.wait()?;
+            return Err(Error::new(
+                ErrorKind::TimedOut,
+                format!("Command took longer than {} seconds", timeout.as_secs()),
+            ));
+        }
+    };
+    let output_lines = stdout_handle.join().unwrap_or(Ok(Vec::new()))?;
+    let errors = stderr_handle.join().unwrap_or_default();
     if !status.success() {
-        return Err(Error::new(
-            ErrorKind::Other,
-            format!("Command executed with non-zero exit status: {}", status),
+        return Err(Error::other(
+            format!("Command executed with non-zero exit status: {}\n{}", status, errors.trim_end()),
         ));
     }
 
@@ -1,20 +1,165 @@
-use crate::{models::errors::MatchMakerError, db::operations_competition::get_running_competitions};
+use std::{collections::HashMap, sync::{Mutex, atomic::{AtomicBool, Ordering}}, thread};
 
-use super::matchmaker_2v2::run_2v2_round;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
 
+use crate::{
+    models::errors::MatchMakerError,
+    db::{
+        operations_competition::{get_running_competitions, get_competition_by_id},
+        operations_match_job::{get_match_jobs, get_unsettled_job_competition_ids}
+    }
+};
+
+use super::{matchmaker_2v2::run_2v2_round, schedule::parse_schedule};
+
+/// Time of the last round of every competition seen by the scheduler.
+static LAST_RUNS: Lazy<Mutex<HashMap<String, DateTime<Utc>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Set while rounds are being played, so ticks never overlap.
+static ROUNDS_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Starts a round of a 2v2 competition right away, on a separate thread.
+///
+/// The competition's schedule starts over from now, so the scheduler does not play another
+/// round right after this one.
+///
+/// # Arguments
+///
+/// * `competition_id` - The ID of the competition to play a round for.
+///
+/// # Returns
+///
+/// `false` if no round was started because other rounds are still being played.
+///
+pub fn start_competition_round(competition_id: String) -> bool {
+    if ROUNDS_RUNNING.swap(true, Ordering::SeqCst) {
+        return false;
+    }
+    LAST_RUNS
+        .lock()
+        .expect("Last runs mutex is poisoned")
+        .insert(competition_id.clone(), Utc::now());
+
+    thread::spawn(move || {
+        if let Err(e) = run_2v2_round(competition_id.clone()) {
+            eprintln!("Error on running round of competition {}: {:?}", competition_id, e);
+        }
+        ROUNDS_RUNNING.store(false, Ordering::SeqCst);
+    });
+    true
+}
 
+/// Runs a round for every running competition whose schedule is due.
+///
+/// The schedules are read from the database on every call, so changes are picked up without
+/// a restart. A competition seen for the first time starts its schedule from now, it does not
+/// play a round right away. Rounds are played one after the other, and if the rounds of the
+/// previous call are still being played this call does nothing: due competitions are then
+/// picked up by the next call.
+///
+/// # Errors
+///
+/// Returns a `MatchMakerError` if the running competitions could not be fetched. Failing rounds
+/// are logged and do not stop the rounds of the other competitions.
+///
+pub fn run_due_competitions() -> Result<(), MatchMakerError> {
+    if ROUNDS_RUNNING.swap(true, Ordering::SeqCst) {
+        println!("Previous rounds are still running, skipping this tick");
+        return Ok(());
+    }
+    let result = run_due_rounds();
+    ROUNDS_RUNNING.store(false, Ordering::SeqCst);
+    result
+}
 
-pub fn run_competitions_round() -> Result<(), MatchMakerError> {
+fn run_due_rounds() -> Result<(), MatchMakerError> {
     let competitions = match get_running_competitions() {
         Ok(c) => c,
         Err(e) => return Err(MatchMakerError::DatabaseError(e)),
     };
-    
+
     for competition in competitions.into_iter() {
-        match competition.type_.as_str() {
-            "2v2" => run_2v2_round(competition.id)?,
+        let schedule = match parse_schedule(&competition.schedule) {
+            Some(s) => s,
+            None => {
+                eprintln!("Invalid schedule '{}' of competition {}", competition.schedule, competition.id);
+                continue;
+            }
+        };
+
+        // rounds of other competitions may have taken a while
+        let now = Utc::now();
+        {
+            let mut last_runs = LAST_RUNS.lock().expect("Last runs mutex is poisoned");
+            let last_run = last_runs.entry(competition.id.clone()).or_insert(now);
+            if !schedule.is_due(last_run, &now) {
+                continue;
+            }
+            *last_run = now;
+        }
+
+        let result = match competition.type_.as_str() {
+            "2v2" => run_2v2_round(competition.id.clone()),
             _ => continue,
+        };
+        if let Err(e) = result {
+            eprintln!("Error on running round of competition {}: {:?}", competition.id, e);
+        }
+    }
+    Ok(())
+}
+
+
+/// Resumes the rounds that were interrupted, e.g. because the process died in the middle of a round.
+///
+/// A round is interrupted if its competition still has match jobs for its current round: either
+/// some jobs were not settled, or the round was never finalized. The rounds are run again, which
+/// only plays the unsettled jobs.
+///
+/// # Errors
+///
+/// Returns a `MatchMakerError` if the competitions or their jobs cannot be fetched. Failing
+/// rounds are logged and do not stop the other rounds from being resumed.
+///
+pub fn resume_unfinished_rounds() -> Result<(), MatchMakerError> {
+    if ROUNDS_RUNNING.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+    let result = resume_rounds();
+    ROUNDS_RUNNING.store(false, Ordering::SeqCst);
+    result
+}
+
+fn resume_rounds() -> Result<(), MatchMakerError> {
+    let mut competition_ids = match get_unsettled_job_competition_ids() {
+        Ok(ids) => ids,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e)),
+    };
+    // rounds with only settled jobs may still miss their finalization
+    match get_running_competitions() {
+        Ok(c) => competition_ids.extend(c.into_iter().map(|competition| competition.id)),
+        Err(e) => return Err(MatchMakerError::DatabaseError(e)),
+    };
+    competition_ids.sort();
+    competition_ids.dedup();
+
+    for competition_id in competition_ids.into_iter() {
+        let competition = match get_competition_by_id(competition_id) {
+            Ok(c) => c,
+            Err(e) => return Err(MatchMakerError::DatabaseError(e)),
+        };
+        let jobs = match get_match_jobs(competition.id.clone(), competition.round) {
+            Ok(jobs) => jobs,
+            Err(e) => return Err(MatchMakerError::DatabaseError(e)),
+        };
+        if jobs.is_empty() {
+            continue;
+        }
+
+        if let Err(e) = run_2v2_round(competition.id.clone()) {
+            eprintln!("Error on resuming round of competition {}: {:?}", competition.id, e);
         }
     }
     Ok(())
-}
\ No newline at end of file
+}
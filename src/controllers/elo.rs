@@ -16,7 +16,23 @@ pub fn update_team_elo(games: Vec<Game2v2>) -> Result<(), Error> {
     Ok(())
 }
 
-pub fn calc_elo_changes(game: &mut NewGame2v2) -> Result<(), Error> {
+/// Calculates the ELO changes of the teams of a game.
+///
+/// A drawn game scores 0.5 for both teams. Games that did not finish (their outcome is
+/// `timeout` or `aborted`) are rated according to the competition's timeout policy: `score`
+/// rates them like finished games, `draw` rates them as a draw and `void` leaves the ELO
+/// unchanged. Drawn and void games have no winner, they are marked as a draw or as void.
+pub fn calc_elo_changes(game: &mut NewGame2v2, timeout_policy: &str) -> Result<(), Error> {
+    let policy = if game.outcome.eq("finished") { "score" } else { timeout_policy };
+    if policy.eq("void") {
+        game.winner_id = "".to_string();
+        game.draw = false;
+        game.void = true;
+        game.team1_elo = 0;
+        game.team2_elo = 0;
+        return Ok(());
+    }
+
     let team1 = match get_team_by_id(game.team1_id.clone()) {
         Ok(t) => t,
         Err(e) => return Err(e),
@@ -26,7 +42,18 @@ pub fn calc_elo_changes(game: &mut NewGame2v2) -> Result<(), Error> {
         Err(e) => return Err(e),
     };
 
-    let result_team1 = if game.winner_id == game.team1_id { 1.0 } else { 0.0 };
+    if policy.eq("draw") {
+        game.winner_id = "".to_string();
+        game.draw = true;
+    }
+
+    let result_team1 = if game.draw {
+        0.5
+    } else if game.winner_id == game.team1_id {
+        1.0
+    } else {
+        0.0
+    };
     let result_team2 = 1.0 - result_team1; // Opposite of team1's result
 
     game.team1_elo = calculate_elo_change(team1.elo, team2.elo, result_team1);
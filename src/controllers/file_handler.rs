@@ -1,9 +1,22 @@
-use std::{fs::File, io::Write};
+use std::{fs::{self, File}, io::{self, Cursor, Read, Seek, Write}, os::unix::fs::PermissionsExt, path::{Component, Path}};
 
-use zip::{write::FileOptions, CompressionMethod};
+use sha2::{Digest, Sha256};
+use zip::{write::FileOptions, CompressionMethod, ZipArchive};
 
 use crate::models::errors::MatchMakerError;
 
+/// Most entries (files and directories) a zip may hold to be extracted.
+pub const MAX_ZIP_ENTRIES: usize = 2000;
+
+/// Most bytes the files of a zip may add up to once extracted.
+pub const MAX_UNZIPPED_SIZE: u64 = 100 * 1024 * 1024;
+
+/// Highest compression ratio of a file in a zip. Sources compress by 10:1 at most, much higher
+/// ratios are a sign of a zip bomb. Files below `RATIO_MIN_SIZE` are not checked.
+pub const MAX_COMPRESSION_RATIO: u64 = 100;
+
+const RATIO_MIN_SIZE: u64 = 1024 * 1024;
+
 
 
 pub fn save_to_zip(contents: String, file_name: &str) -> Result<(), MatchMakerError> {
@@ -27,4 +40,313 @@ pub fn save_to_zip(contents: String, file_name: &str) -> Result<(), MatchMakerEr
     // Finish writing the zip file
     zip.finish().map_err(|e| MatchMakerError::ZippingError(e.into()))?;
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Zips a directory (recursively) into memory.
+///
+/// Paths inside the archive are relative to the zipped directory.
+pub fn zip_directory(directory: &Path) -> Result<Vec<u8>, MatchMakerError> {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = FileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .unix_permissions(0o755);
+
+    let mut pending = vec![directory.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for entry in fs::read_dir(&current).map_err(MatchMakerError::IOError)? {
+            let path = entry.map_err(MatchMakerError::IOError)?.path();
+            let name = match path.strip_prefix(directory) {
+                Ok(name) => name.to_string_lossy().to_string(),
+                Err(_) => return Err(MatchMakerError::InvalidPath(path.into_boxed_path())),
+            };
+            if path.is_dir() {
+                zip.add_directory(name, options)
+                    .map_err(MatchMakerError::ZippingError)?;
+                pending.push(path);
+            } else {
+                zip.start_file(name, options)
+                    .map_err(MatchMakerError::ZippingError)?;
+                zip.write_all(&fs::read(&path).map_err(MatchMakerError::IOError)?)
+                    .map_err(MatchMakerError::IOError)?;
+            }
+        }
+    }
+
+    let cursor = zip.finish().map_err(MatchMakerError::ZippingError)?;
+    Ok(cursor.into_inner())
+}
+
+/// Extracts a zip archive held in memory into a directory, see `extract_zip`.
+pub fn unzip_to_directory(contents: Vec<u8>, directory: &Path) -> Result<(), MatchMakerError> {
+    let mut archive = ZipArchive::new(Cursor::new(contents))
+        .map_err(MatchMakerError::ZippingError)?;
+    extract_zip(&mut archive, directory)
+}
+
+/// Checks that a zip is safe to extract, without extracting it.
+///
+/// A zip is rejected if an entry has an absolute path (including Windows drive letters and UNC
+/// paths) or a `..` component (it would be extracted outside of the target directory), it holds more than `MAX_ZIP_ENTRIES` entries, its files add
+/// up to more than `MAX_UNZIPPED_SIZE` bytes, or a file is compressed by more than
+/// `MAX_COMPRESSION_RATIO`.
+///
+/// # Errors
+///
+/// Returns a `MatchMakerError::UnsafeZip` with the reason the zip was rejected, or a
+/// `MatchMakerError::ZippingError` if the zip is corrupt.
+///
+pub fn check_zip<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Result<(), MatchMakerError> {
+    if archive.len() > MAX_ZIP_ENTRIES {
+        return Err(MatchMakerError::UnsafeZip(format!(
+            "The zip holds {} entries, at most {} are allowed",
+            archive.len(),
+            MAX_ZIP_ENTRIES
+        )));
+    }
+
+    let mut total_size: u64 = 0;
+    for i in 0..archive.len() {
+        let file = archive.by_index(i).map_err(MatchMakerError::ZippingError)?;
+        check_entry_path(file.name())?;
+
+        total_size = total_size.saturating_add(file.size());
+        if total_size > MAX_UNZIPPED_SIZE {
+            return Err(unzipped_size_error());
+        }
+        if file.size() >= RATIO_MIN_SIZE && file.size() / file.compressed_size().max(1) > MAX_COMPRESSION_RATIO {
+            return Err(MatchMakerError::UnsafeZip(format!(
+                "Entry {} is compressed by more than {}:1",
+                file.name(),
+                MAX_COMPRESSION_RATIO
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Extracts a zip into a directory, after checking it with `check_zip`.
+///
+/// The sizes stored in a zip may lie, so the size limit is enforced on the extracted bytes as
+/// well. Permissions of the files are kept, apart from special bits.
+///
+/// # Errors
+///
+/// Returns a `MatchMakerError::UnsafeZip` with the reason the zip was rejected, a
+/// `MatchMakerError::ZippingError` if the zip is corrupt, or a `MatchMakerError::IOError` if
+/// the files cannot be written. Files extracted before the error are left in place.
+///
+pub fn extract_zip<R: Read + Seek>(archive: &mut ZipArchive<R>, directory: &Path) -> Result<(), MatchMakerError> {
+    check_zip(archive)?;
+
+    let mut remaining = MAX_UNZIPPED_SIZE;
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).map_err(MatchMakerError::ZippingError)?;
+        let path = directory.join(check_entry_path(file.name())?);
+
+        if file.is_dir() {
+            fs::create_dir_all(&path).map_err(MatchMakerError::IOError)?;
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(MatchMakerError::IOError)?;
+        }
+        let mut output = File::create(&path).map_err(MatchMakerError::IOError)?;
+        let written = io::copy(&mut (&mut file).take(remaining + 1), &mut output)
+            .map_err(MatchMakerError::IOError)?;
+        if written > remaining {
+            return Err(unzipped_size_error());
+        }
+        remaining -= written;
+
+        if let Some(mode) = file.unix_mode() {
+            fs::set_permissions(&path, fs::Permissions::from_mode(mode & 0o777))
+                .map_err(MatchMakerError::IOError)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the path of a zip entry, if it stays inside the directory the zip is extracted to.
+fn check_entry_path(name: &str) -> Result<&Path, MatchMakerError> {
+    let path = Path::new(name);
+    if name.contains('\0') {
+        return Err(MatchMakerError::UnsafeZip(format!("Entry {:?} has an invalid name", name)));
+    }
+    // drive letters and UNC paths are no prefixes on Unix, but they are absolute on Windows
+    let bytes = name.as_bytes();
+    if name.starts_with('\\') || (bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':') {
+        return Err(MatchMakerError::UnsafeZip(format!("Entry {} has an absolute path", name)));
+    }
+    for component in path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => return Err(MatchMakerError::UnsafeZip(format!(
+                "Entry {} has an absolute path",
+                name
+            ))),
+            Component::ParentDir => return Err(MatchMakerError::UnsafeZip(format!(
+                "Entry {} contains '..'",
+                name
+            ))),
+            Component::CurDir | Component::Normal(_) => (),
+        }
+    }
+    Ok(path)
+}
+
+fn unzipped_size_error() -> MatchMakerError {
+    MatchMakerError::UnsafeZip(format!(
+        "The zip unpacks to more than {} MB",
+        MAX_UNZIPPED_SIZE / (1024 * 1024)
+    ))
+}
+
+/// Reads the game output saved with `save_to_zip`, the first file of the zip.
+pub fn read_zipped_log(file_name: &str) -> Result<String, MatchMakerError> {
+    let file = File::open(file_name).map_err(MatchMakerError::IOError)?;
+    let mut zip = ZipArchive::new(file).map_err(MatchMakerError::ZippingError)?;
+    let mut log_file = zip.by_index(0).map_err(MatchMakerError::ZippingError)?;
+
+    let mut contents = String::new();
+    log_file.read_to_string(&mut contents).map_err(MatchMakerError::IOError)?;
+    Ok(contents)
+}
+
+/// Returns the SHA-256 hash of a file, hex encoded.
+pub fn sha256_file(path: &Path) -> Result<String, io::Error> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, path::PathBuf};
+
+    use uuid::Uuid;
+    use zip::ZipWriter;
+
+    use super::*;
+
+    /// Creates an empty directory for a test.
+    fn temp_dir() -> PathBuf {
+        let directory = env::temp_dir().join(format!("file_handler_{}", Uuid::new_v4()));
+        fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    /// Writes `bot.zip` into `directory`, holding a file of zeros of the given size per entry.
+    fn write_zip(directory: &Path, entries: &[(&str, usize)]) -> PathBuf {
+        let path = directory.join("bot.zip");
+        let mut zip = ZipWriter::new(File::create(&path).unwrap());
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+        let chunk = vec![0; 1024 * 1024];
+        for (name, size) in entries {
+            zip.start_file(*name, options).unwrap();
+            let mut left = *size;
+            while left > 0 {
+                let written = left.min(chunk.len());
+                zip.write_all(&chunk[..written]).unwrap();
+                left -= written;
+            }
+        }
+        zip.finish().unwrap();
+        path
+    }
+
+    /// Overwrites the uncompressed size of the only file of a zip, in its local header and in
+    /// the central directory.
+    fn declare_size(path: &Path, size: u32) {
+        let mut bytes = fs::read(path).unwrap();
+        for (signature, offset) in [([0x50, 0x4b, 0x03, 0x04], 22), ([0x50, 0x4b, 0x01, 0x02], 24)] {
+            let header = bytes.windows(4).position(|window| window == signature).unwrap();
+            bytes[header + offset..header + offset + 4].copy_from_slice(&size.to_le_bytes());
+        }
+        fs::write(path, bytes).unwrap();
+    }
+
+    fn open(path: &Path) -> ZipArchive<File> {
+        ZipArchive::new(File::open(path).unwrap()).unwrap()
+    }
+
+    fn rejection(result: Result<(), MatchMakerError>) -> String {
+        match result {
+            Err(MatchMakerError::UnsafeZip(reason)) => reason,
+            other => panic!("expected the zip to be rejected, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn extracts_safe_zips() {
+        let directory = temp_dir();
+        let zip = write_zip(&directory, &[("src/Player.java", 64), ("./bot.json", 2)]);
+        extract_zip(&mut open(&zip), &directory.join("bot")).unwrap();
+        assert_eq!(fs::read(directory.join("bot/src/Player.java")).unwrap().len(), 64);
+        assert!(directory.join("bot/bot.json").is_file());
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn rejects_absolute_paths() {
+        let directory = temp_dir();
+        let zip = write_zip(&directory, &[("Player.java", 64), ("/tmp/evil.sh", 64)]);
+        assert!(rejection(check_zip(&mut open(&zip))).contains("absolute path"));
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn rejects_prefixed_paths() {
+        for name in ["C:/Windows/evil.bat", "c:evil.bat", "\\\\server\\share\\evil.bat"] {
+            let directory = temp_dir();
+            let zip = write_zip(&directory, &[(name, 64)]);
+            assert!(rejection(check_zip(&mut open(&zip))).contains("absolute path"), "{} was accepted", name);
+            fs::remove_dir_all(&directory).unwrap();
+        }
+    }
+
+    #[test]
+    fn rejects_parent_directories() {
+        let directory = temp_dir();
+        let zip = write_zip(&directory, &[("src/../../evil.sh", 64)]);
+        assert!(rejection(extract_zip(&mut open(&zip), &directory.join("bot"))).contains("'..'"));
+        assert!(!directory.join("evil.sh").exists());
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn rejects_too_many_entries() {
+        let directory = temp_dir();
+        let names: Vec<String> = (0..=MAX_ZIP_ENTRIES).map(|i| format!("file{}.txt", i)).collect();
+        let entries: Vec<(&str, usize)> = names.iter().map(|name| (name.as_str(), 0)).collect();
+        let zip = write_zip(&directory, &entries);
+        assert!(rejection(check_zip(&mut open(&zip))).contains("entries"));
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn rejects_large_zips() {
+        let directory = temp_dir();
+        let zip = write_zip(&directory, &[("Player.java", 64)]);
+        declare_size(&zip, MAX_UNZIPPED_SIZE as u32 + 1);
+        assert!(rejection(check_zip(&mut open(&zip))).contains("unpacks to more than"));
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn rejects_high_compression_ratios() {
+        let directory = temp_dir();
+        let zip = write_zip(&directory, &[("zeros.txt", 2 * RATIO_MIN_SIZE as usize)]);
+        assert!(rejection(check_zip(&mut open(&zip))).contains("compressed by more than"));
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn rejects_understated_sizes() {
+        let directory = temp_dir();
+        let zip = write_zip(&directory, &[("zeros.txt", MAX_UNZIPPED_SIZE as usize + 1024)]);
+        declare_size(&zip, 1024);
+        assert!(check_zip(&mut open(&zip)).is_ok());
+        assert!(rejection(extract_zip(&mut open(&zip), &directory.join("bot"))).contains("unpacks to more than"));
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,270 @@
+use std::{cmp::Reverse, collections::HashMap, sync::Mutex, thread};
+
+use crate::{
+    db::{
+        operations_competition::get_competition_by_id,
+        operations_teams::get_teams_by_competition_id,
+        operations_game2v2::get_games_by_evaluation_id,
+        operations_final_evaluation::{insert_final_evaluation, get_final_evaluations_by_status, set_final_evaluation_status, insert_final_standings}
+    },
+    models::{
+        errors::MatchMakerError,
+        final_evaluation::{FinalEvaluation, NewFinalEvaluation, NewFinalStanding},
+        game_2v2::{NewGame2v2, Game2v2},
+        team::Team
+    }
+};
+
+use super::matchmaker_2v2::{compile_team_bots, run_matches};
+
+/// IDs of the competitions whose final evaluation is being played.
+static EVALUATIONS_RUNNING: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Starts the final evaluation of a competition.
+///
+/// The evaluation is stored right away with the `running` status, while the games are played
+/// on a separate thread. Once all games are played, the standings of the evaluation are stored
+/// and its status is set to `done` (or `failed` if the evaluation could not be completed). An
+/// evaluation interrupted by a restart is picked up again, see `resume_final_evaluations`.
+///
+/// # Arguments
+///
+/// * `competition_id` - The ID of the competition to evaluate.
+/// * `games_per_pair` - The number of games every pair of teams plays against each other.
+///
+/// # Returns
+///
+/// A `Result` containing the started `FinalEvaluation`, or a `MatchMakerError` if the
+/// competition does not exist, an evaluation of the competition is already running
+/// (`MatchMakerError::AlreadyRunning`) or the evaluation could not be stored.
+///
+pub fn start_final_evaluation(competition_id: String, games_per_pair: i32) -> Result<FinalEvaluation, MatchMakerError> {
+    let competition = match get_competition_by_id(competition_id) {
+        Ok(c) => c,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+
+    {
+        let mut running = EVALUATIONS_RUNNING.lock().expect("Evaluations mutex is poisoned");
+        if running.contains(&competition.id) {
+            return Err(MatchMakerError::AlreadyRunning(format!(
+                "A final evaluation of competition {} is already running",
+                competition.id
+            )));
+        }
+        running.push(competition.id.clone());
+    }
+
+    let evaluation = match insert_final_evaluation(NewFinalEvaluation {
+        competition_id: competition.id.clone(),
+        games_per_pair,
+    }) {
+        Ok(e) => e,
+        Err(e) => {
+            finish_running(&competition.id);
+            return Err(MatchMakerError::DatabaseError(e))
+        }
+    };
+
+    spawn_final_evaluation(evaluation.clone());
+    Ok(evaluation)
+}
+
+/// Resumes the final evaluations that were interrupted, e.g. because the process died while
+/// their games were played.
+///
+/// Every evaluation still `running` is played again on a separate thread, which only plays the
+/// games that were not stored yet (see `unplayed_games`). It must only be called on startup,
+/// before any evaluation is started.
+///
+/// # Errors
+///
+/// Returns a `MatchMakerError` if the running evaluations cannot be fetched.
+///
+pub fn resume_final_evaluations() -> Result<(), MatchMakerError> {
+    let evaluations = match get_final_evaluations_by_status("running".to_string()) {
+        Ok(evaluations) => evaluations,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+    for evaluation in evaluations.into_iter() {
+        println!("Resuming final evaluation {} of competition {}", evaluation.id, evaluation.competition_id);
+        EVALUATIONS_RUNNING
+            .lock()
+            .expect("Evaluations mutex is poisoned")
+            .push(evaluation.competition_id.clone());
+        spawn_final_evaluation(evaluation);
+    }
+    Ok(())
+}
+
+fn spawn_final_evaluation(evaluation: FinalEvaluation) {
+    thread::spawn(move || {
+        let status = match run_final_evaluation(&evaluation) {
+            Ok(_) => "done",
+            Err(e) => {
+                eprintln!("Error on running final evaluation {}: {:?}", evaluation.id, e);
+                "failed"
+            }
+        };
+        if let Err(e) = set_final_evaluation_status(evaluation.id.clone(), status.to_string()) {
+            eprintln!("Error on setting final evaluation status: {:?}", e);
+        }
+        finish_running(&evaluation.competition_id);
+    });
+}
+
+fn finish_running(competition_id: &str) {
+    EVALUATIONS_RUNNING
+        .lock()
+        .expect("Evaluations mutex is poisoned")
+        .retain(|id| id != competition_id);
+}
+
+/// Plays a full round-robin between the teams of the evaluated competition and stores
+/// the resulting standings.
+///
+/// Games are played through the same machinery as the ladder rounds, but they are tagged
+/// with the evaluation ID and never change the ladder ELO of the teams. Games of the evaluation
+/// that were already stored are not played again.
+fn run_final_evaluation(evaluation: &FinalEvaluation) -> Result<(), MatchMakerError> {
+    println!("Running final evaluation: {}", evaluation.id);
+    let competition = match get_competition_by_id(evaluation.competition_id.clone()) {
+        Ok(c) => c,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+    let teams = match get_teams_by_competition_id(competition.id.clone()) {
+        Ok(teams) => teams,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+
+    // the teams are ordered by id, so a resumed evaluation plans the same games
+    let mut compiled_teams = compile_team_bots(teams.clone(), &competition.language);
+    compiled_teams.sort_by(|a, b| a.id.cmp(&b.id));
+    let played = match get_games_by_evaluation_id(evaluation.id.clone()) {
+        Ok(games) => games,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+    run_matches(&competition, unplayed_games(create_round_robin_games(evaluation, &compiled_teams), &played));
+
+    let games = match get_games_by_evaluation_id(evaluation.id.clone()) {
+        Ok(games) => games,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+
+    let standings = rank_final_standings(evaluation, &teams, games);
+    if let Err(e) = insert_final_standings(standings) {
+        return Err(MatchMakerError::DatabaseError(e))
+    }
+    println!("Final evaluation done!");
+    Ok(())
+}
+
+/// Creates the games of a full round-robin: every pair of teams plays `games_per_pair` games.
+///
+/// Seats are rotated between the games of a pair: every other game the teams switch sides,
+/// and every other pair of games the bots of each team switch seats. The games belong to no
+/// ladder round (their round is 0), they are found by the evaluation ID.
+fn create_round_robin_games(evaluation: &FinalEvaluation, teams: &[Team]) -> Vec<NewGame2v2> {
+    let mut games = Vec::new();
+    for (index, first) in teams.iter().enumerate() {
+        for second in teams.iter().skip(index + 1) {
+            for game in 0..evaluation.games_per_pair {
+                let (team1, team2) = if game % 2 == 1 { (second, first) } else { (first, second) };
+                let (team1, team2) = if (game / 2) % 2 == 1 {
+                    (swap_bot_seats(team1), swap_bot_seats(team2))
+                } else {
+                    (team1.clone(), team2.clone())
+                };
+
+                let mut match_game = NewGame2v2::from_teams(
+                    evaluation.competition_id.clone(),
+                    0,
+                    &team1,
+                    &team2,
+                );
+                match_game.evaluation_id = evaluation.id.clone();
+                games.push(match_game);
+            }
+        }
+    }
+    games
+}
+
+/// Leaves out the planned games that were already played. A played game stands in for one
+/// planned game with the same teams and the same bots in the same seats.
+fn unplayed_games(planned: Vec<NewGame2v2>, played: &[Game2v2]) -> Vec<NewGame2v2> {
+    let mut played_seats: HashMap<[String; 6], usize> = HashMap::new();
+    for game in played.iter() {
+        let seats = [&game.team1_id, &game.team2_id, &game.team1bot1_id, &game.team1bot2_id, &game.team2bot1_id, &game.team2bot2_id];
+        *played_seats.entry(seats.map(String::clone)).or_insert(0) += 1;
+    }
+
+    planned
+        .into_iter()
+        .filter(|game| {
+            let seats = [&game.team1_id, &game.team2_id, &game.team1bot1_id, &game.team1bot2_id, &game.team2bot1_id, &game.team2bot2_id];
+            match played_seats.get_mut(&seats.map(String::clone)) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                },
+                _ => true,
+            }
+        })
+        .collect()
+}
+
+fn swap_bot_seats(team: &Team) -> Team {
+    let mut swapped = team.clone();
+    swapped.bot1 = team.bot2.clone();
+    swapped.bot2 = team.bot1.clone();
+    swapped
+}
+
+/// Ranks the teams by the number of games won in the evaluation (fewer losses break ties, so
+/// a draw ranks above a loss). Void games are not counted at all. Teams that did not play
+/// (e.g. because their bots did not compile) are ranked last.
+fn rank_final_standings(evaluation: &FinalEvaluation, teams: &[Team], games: Vec<Game2v2>) -> Vec<NewFinalStanding> {
+    // wins, losses, draws and games played of each team
+    let mut records: HashMap<String, (i32, i32, i32, i32)> = teams
+        .iter()
+        .map(|team| (team.id.clone(), (0, 0, 0, 0)))
+        .collect();
+
+    for game in games.into_iter().filter(|game| !game.void) {
+        for team_id in [&game.team1_id, &game.team2_id] {
+            if let Some(record) = records.get_mut(team_id) {
+                record.3 += 1;
+                if game.winner_id.eq(team_id) {
+                    record.0 += 1;
+                } else if game.draw {
+                    record.2 += 1;
+                } else {
+                    record.1 += 1;
+                }
+            }
+        }
+    }
+
+    let mut records: Vec<(String, (i32, i32, i32, i32))> = records.into_iter().collect();
+    records.sort_by_key(|(_, (wins, losses, _, games_played))| (*games_played == 0, Reverse(*wins), *losses));
+
+    let mut standings: Vec<NewFinalStanding> = Vec::new();
+    for (index, (team_id, (wins, losses, draws, games_played))) in records.into_iter().enumerate() {
+        let team_rank = match standings.last() {
+            Some(previous) if previous.games_played > 0 && previous.wins == wins && previous.losses == losses => previous.team_rank,
+            Some(previous) if previous.games_played == 0 && games_played == 0 => previous.team_rank,
+            _ => index as i32 + 1,
+        };
+        standings.push(NewFinalStanding {
+            evaluation_id: evaluation.id.clone(),
+            team_id,
+            team_rank,
+            wins,
+            losses,
+            draws,
+            games_played,
+        });
+    }
+    standings
+}
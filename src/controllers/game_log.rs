@@ -0,0 +1,613 @@
+use std::{cmp::Ordering, collections::HashMap, fmt};
+
+use serde::Serialize;
+
+use crate::models::game_player_stats::GamePlayerStats;
+
+/// Keys of the players of a game, in the order their bots are passed to the evaluator.
+pub const PLAYER_KEYS: [&str; 4] = ["team1bot1", "team1bot2", "team2bot1", "team2bot2"];
+
+/// Order the evaluator prints the `STAT:` blocks of the players in, as indexes into
+/// `PLAYER_KEYS`. Used for blocks whose header does not name the player's bot.
+const STAT_ORDER: [usize; 4] = [0, 2, 1, 3];
+
+/// The color of a player in the evaluator's output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Color {
+    Green,
+    Blue,
+    Yellow,
+    Cyan,
+}
+
+impl Color {
+    fn parse(name: &str) -> Option<Color> {
+        match name {
+            "green" => Some(Color::Green),
+            "blue" => Some(Color::Blue),
+            "yellow" => Some(Color::Yellow),
+            "cyan" => Some(Color::Cyan),
+            _ => None,
+        }
+    }
+
+    /// Returns the team playing the color, 1 or 2.
+    pub fn team(&self) -> u8 {
+        match self {
+            Color::Yellow | Color::Green => 1,
+            Color::Blue | Color::Cyan => 2,
+        }
+    }
+}
+
+/// An event in the output of a game.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    /// `R {score} {color}`: the score of a color changed.
+    Score { color: Color, score: i32 },
+    /// A line starting with `L `: a player lost, e.g. because its bot stopped responding.
+    Loss(String),
+    /// A `STAT: {header}` line and the `{key}: {value}` lines following it.
+    Stats { header: String, stats: GamePlayerStats },
+    /// A line the game wrote to stderr.
+    Error(String),
+}
+
+/// A line of the evaluator's output that looks like an event, but cannot be read.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameLogError {
+    /// Number of the line in stdout, starting at 1.
+    pub line: usize,
+    pub content: String,
+    pub reason: String,
+}
+
+impl fmt::Display for GameLogError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Line {} of the game output ({:?}): {}", self.line, self.content, self.reason)
+    }
+}
+
+/// The result of a game according to its output. Players are indexes into `PLAYER_KEYS`.
+#[derive(Debug, PartialEq)]
+pub enum GameOutcome {
+    /// The game was played until one team was left, or until it timed out.
+    Played {
+        survived: [bool; 4],
+        /// The team that won, 1 or 2. If both teams survived, it is the team with the higher
+        /// score, `None` (a draw) on equal scores.
+        winner: Option<u8>,
+        stats: Box<[Option<GamePlayerStats>; 4]>,
+    },
+    /// A bot broke the game, e.g. it crashed or wrote to stderr.
+    Bugged {
+        /// The player to blame, if its bot is named in the error.
+        blame: Option<usize>,
+        error: String,
+    },
+}
+
+/// Reads the events in the output of a game.
+///
+/// Lines that are no events (e.g. planets and fleets) are skipped. Every line written to stderr
+/// is an error event, after the events of stdout.
+///
+/// # Returns
+///
+/// The events, and a `GameLogError` for every line that starts like an event but cannot be read,
+/// e.g. a score that is not a number or an unknown statistic. Such lines are skipped, so a change
+/// of the evaluator's output format only loses the lines it touches, not the whole game.
+///
+pub fn parse_events(lines: &[String], errors: &[String]) -> (Vec<GameEvent>, Vec<GameLogError>) {
+    let mut events = Vec::new();
+    let mut skipped = Vec::new();
+    // statistics follow a `STAT:` line until the next one
+    let mut current_stats: Option<(String, GamePlayerStats)> = None;
+
+    for (index, line) in lines.iter().enumerate() {
+        let error = |reason: &str| GameLogError { line: index + 1, content: line.clone(), reason: reason.to_string() };
+        let parts: Vec<&str> = line.split_whitespace().collect();
+
+        if let Some(header) = line.strip_prefix("STAT:") {
+            if let Some((header, stats)) = current_stats.take() {
+                events.push(GameEvent::Stats { header, stats });
+            }
+            current_stats = Some((header.trim().to_string(), GamePlayerStats::default()));
+        } else if line.starts_with("R ") {
+            if parts.len() != 3 {
+                skipped.push(error("expected 'R {score} {color}'"));
+                continue;
+            }
+            let Ok(score) = parts[1].parse() else {
+                skipped.push(error("the score is not a number"));
+                continue;
+            };
+            let Some(color) = Color::parse(parts[2]) else {
+                skipped.push(error("unknown color"));
+                continue;
+            };
+            events.push(GameEvent::Score { color, score });
+        } else if line.starts_with("L ") {
+            events.push(GameEvent::Loss(line.clone()));
+        } else if let Some((_, stats)) = current_stats.as_mut() {
+            if parts.len() == 2 && parts[0].ends_with(':') {
+                if let Err(reason) = set_stat(stats, parts[0].trim_end_matches(':'), parts[1]) {
+                    skipped.push(error(&reason));
+                }
+            }
+        }
+    }
+    if let Some((header, stats)) = current_stats {
+        events.push(GameEvent::Stats { header, stats });
+    }
+
+    events.extend(errors.iter().map(|line| GameEvent::Error(line.clone())));
+    (events, skipped)
+}
+
+/// Decides the outcome of a game from its events.
+///
+/// The game is bugged if it wrote more than one line to stderr (the evaluator always writes
+/// one), or if no statistics were printed but a player lost. The blame goes to the player whose
+/// bot ID is named last in the errors, or in the loss.
+///
+/// # Arguments
+///
+/// * `events` - The events of the game, see `parse_events`.
+/// * `bot_ids` - The IDs of the game's bots, in the order of `PLAYER_KEYS`.
+///
+pub fn game_outcome(events: &[GameEvent], bot_ids: &[String; 4]) -> GameOutcome {
+    let errors: Vec<&String> = events
+        .iter()
+        .filter_map(|event| match event {
+            GameEvent::Error(line) => Some(line),
+            _ => None,
+        })
+        .collect();
+    if errors.len() > 1 {
+        return bugged(&errors, bot_ids);
+    }
+
+    let mut last_loss: Option<&String> = None;
+    let mut stats: [Option<GamePlayerStats>; 4] = Default::default();
+    let mut stat_blocks = 0;
+    for event in events {
+        match event {
+            GameEvent::Loss(line) => last_loss = Some(line),
+            GameEvent::Stats { header, stats: player_stats } => {
+                let player = bot_ids
+                    .iter()
+                    .position(|bot_id| !bot_id.is_empty() && header.contains(bot_id.as_str()))
+                    .or(STAT_ORDER.get(stat_blocks).copied());
+                if let Some(player) = player {
+                    stats[player] = Some(player_stats.clone());
+                }
+                stat_blocks += 1;
+            },
+            GameEvent::Score { .. } | GameEvent::Error(_) => (),
+        }
+    }
+
+    if stat_blocks == 0 {
+        if let Some(loss) = last_loss {
+            return bugged(&[loss], bot_ids);
+        }
+    }
+
+    let survived = [0, 1, 2, 3].map(|player| stats[player].as_ref().is_some_and(|s| s.survived));
+    let team1_alive = survived[0] || survived[1];
+    let team2_alive = survived[2] || survived[3];
+    let winner = match (team1_alive, team2_alive) {
+        (true, false) => Some(1),
+        (false, true) => Some(2),
+        // both teams are alive at the end (timeout), or none is: the score decides
+        _ => {
+            let scores = final_scores(events);
+            match scores[0].cmp(&scores[1]) {
+                Ordering::Greater => Some(1),
+                Ordering::Less => Some(2),
+                Ordering::Equal => None,
+            }
+        },
+    };
+
+    GameOutcome::Played { survived, winner, stats: Box::new(stats) }
+}
+
+/// A planet in a frame of a replay.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReplayPlanet {
+    pub x: f32,
+    pub y: f32,
+    pub size: i32,
+    pub troops: i32,
+    /// `None` for neutral planets.
+    pub owner: Option<Color>,
+}
+
+/// A fleet on its way between two planets, which are indexes into the frame's planets.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReplayFleet {
+    pub troops: i32,
+    pub owner: Option<Color>,
+    pub from: usize,
+    pub to: usize,
+    pub total_turns: i32,
+    pub turns_left: i32,
+}
+
+/// The state of a game after one turn.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReplayFrame {
+    pub turn: usize,
+    pub planets: Vec<ReplayPlanet>,
+    pub fleets: Vec<ReplayFleet>,
+    /// The latest score of every color that has one.
+    pub scores: HashMap<Color, i32>,
+}
+
+/// The turns of a game read by `parse_replay`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Replay {
+    pub frames: Vec<ReplayFrame>,
+    /// Whether the output goes on after the last frame, i.e. reading stopped at the last
+    /// requested turn.
+    pub truncated: bool,
+}
+
+/// Reads the turns of a game from its output, for replays.
+///
+/// The evaluator is expected to print every turn as the planets (`P {x} {y} {size} {troops}
+/// {owner}`), followed by the fleets (`F {troops} {owner} {from} {to} {total_turns}
+/// {turns_left}`) and score updates (`R {score} {color}`). The `P` and `F` formats are not
+/// checked against real evaluator output yet, see `tests/fixtures/game_logs/README.md`.
+///
+/// Turns without fleets and score updates are only their planets, so turns are split by the
+/// number of planets, which never changes during a game. The first turn ends with its first
+/// fleet or score update, or when its first planet shows up again. Owners that are no color
+/// are neutral. Other lines are skipped.
+///
+/// # Arguments
+///
+/// * `lines` - The lines of the game's stdout.
+/// * `last_turn` - The last turn to read, later turns are not parsed. `None` reads all turns.
+///
+/// # Errors
+///
+/// Returns a `GameLogError` for the first planet, fleet or score line that cannot be read.
+///
+pub fn parse_replay(lines: &[String], last_turn: Option<usize>) -> Result<Replay, GameLogError> {
+    let mut frames: Vec<ReplayFrame> = Vec::new();
+    let mut current: Option<ReplayFrame> = None;
+    let mut scores: HashMap<Color, i32> = HashMap::new();
+    let mut planet_count: Option<usize> = None;
+    let mut reading_planets = false;
+
+    for (index, line) in lines.iter().enumerate() {
+        let error = |reason: &str| GameLogError { line: index + 1, content: line.clone(), reason: reason.to_string() };
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let number = |i: usize| parts[i].parse::<f32>().map_err(|_| error("expected a number"));
+        let integer = |i: usize| parts[i].parse::<i32>().map_err(|_| error("expected an integer"));
+
+        match parts.first() {
+            Some(&"P") => {
+                if parts.len() != 6 {
+                    return Err(error("expected 'P {x} {y} {size} {troops} {owner}'"));
+                }
+                let planet = ReplayPlanet {
+                    x: number(1)?,
+                    y: number(2)?,
+                    size: integer(3)?,
+                    troops: integer(4)?,
+                    owner: Color::parse(parts[5]),
+                };
+                let starts_turn = match (&current, planet_count) {
+                    (None, _) => true,
+                    (Some(frame), Some(count)) => frame.planets.len() >= count,
+                    (Some(frame), None) => !reading_planets || frame.planets
+                        .first()
+                        .is_some_and(|first| first.x == planet.x && first.y == planet.y),
+                };
+                if starts_turn {
+                    if let Some(mut frame) = current.take() {
+                        planet_count.get_or_insert(frame.planets.len());
+                        frame.scores = scores.clone();
+                        frames.push(frame);
+                    }
+                    if last_turn.is_some_and(|last| frames.len() > last) {
+                        return Ok(Replay { frames, truncated: true });
+                    }
+                    current = Some(ReplayFrame { turn: frames.len(), planets: Vec::new(), fleets: Vec::new(), scores: HashMap::new() });
+                    reading_planets = true;
+                }
+                if let Some(frame) = current.as_mut() {
+                    frame.planets.push(planet);
+                }
+            },
+            Some(&"F") => {
+                if parts.len() != 7 {
+                    return Err(error("expected 'F {troops} {owner} {from} {to} {total_turns} {turns_left}'"));
+                }
+                let planet = |i: usize| parts[i].parse::<usize>().map_err(|_| error("expected a planet index"));
+                let fleet = ReplayFleet {
+                    troops: integer(1)?,
+                    owner: Color::parse(parts[2]),
+                    from: planet(3)?,
+                    to: planet(4)?,
+                    total_turns: integer(5)?,
+                    turns_left: integer(6)?,
+                };
+                reading_planets = false;
+                if let Some(frame) = current.as_mut() {
+                    frame.fleets.push(fleet);
+                }
+            },
+            Some(&"R") => {
+                if parts.len() != 3 {
+                    return Err(error("expected 'R {score} {color}'"));
+                }
+                let score = integer(1)?;
+                let color = Color::parse(parts[2]).ok_or(error("unknown color"))?;
+                scores.insert(color, score);
+                reading_planets = false;
+            },
+            _ => (),
+        }
+    }
+    if let Some(mut frame) = current {
+        frame.scores = scores;
+        frames.push(frame);
+    }
+    Ok(Replay { frames, truncated: false })
+}
+
+/// Returns the final scores of both teams, the sum of the last scores of their colors.
+fn final_scores(events: &[GameEvent]) -> [i32; 2] {
+    let mut color_scores: Vec<(Color, i32)> = Vec::new();
+    for event in events {
+        if let GameEvent::Score { color, score } = event {
+            color_scores.retain(|(c, _)| c != color);
+            color_scores.push((*color, *score));
+        }
+    }
+    let mut scores = [0; 2];
+    for (color, score) in color_scores {
+        scores[color.team() as usize - 1] += score;
+    }
+    scores
+}
+
+fn bugged(errors: &[&String], bot_ids: &[String; 4]) -> GameOutcome {
+    let blame = errors
+        .iter()
+        .rev()
+        .find_map(|line| bot_ids.iter().position(|bot_id| !bot_id.is_empty() && line.contains(bot_id.as_str())));
+    let error = errors
+        .iter()
+        .map(|line| line.as_str())
+        .collect::<Vec<&str>>()
+        .join("\n");
+    GameOutcome::Bugged { blame, error }
+}
+
+fn set_stat(stats: &mut GamePlayerStats, key: &str, value: &str) -> Result<(), String> {
+    if key.eq("survive") {
+        stats.survived = value.parse().map_err(|_| format!("{} is not a boolean", key))?;
+        return Ok(());
+    }
+
+    let field = match key {
+        "turnsPlayed" => &mut stats.turns_played,
+        "fleetGenerated" => &mut stats.fleet_generated,
+        "fleetLost" => &mut stats.fleet_lost,
+        "fleetReinforced" => &mut stats.fleet_reinforced,
+        "largestAttack" => &mut stats.largest_attack,
+        "largestLoss" => &mut stats.largest_loss,
+        "largestReinforcement" => &mut stats.largest_reinforcement,
+        "planetsLost" => &mut stats.planets_lost,
+        "planetsConquered" => &mut stats.planets_conquered,
+        "planetsDefended" => &mut stats.planets_defended,
+        "planetsAttacked" => &mut stats.planets_attacked,
+        "numFleetLost" => &mut stats.num_fleet_lost,
+        "numFleetReinforced" => &mut stats.num_fleet_reinforced,
+        "numFleetGenerated" => &mut stats.num_fleet_generated,
+        "totalTroopsGenerated" => &mut stats.total_troops_generated,
+        _ => return Err(format!("unknown statistic {}", key)),
+    };
+    *field = value.parse().map_err(|_| format!("{} is not a number", key))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hand-written output of the evaluator for the tests, see `tests/fixtures/game_logs/README.md`.
+    macro_rules! fixture {
+        ($name:literal) => {
+            include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/game_logs/", $name))
+        };
+    }
+
+    fn lines(output: &str) -> Vec<String> {
+        output.lines().map(String::from).collect()
+    }
+
+    fn bot_ids() -> [String; 4] {
+        ["bot-t1b1", "bot-t1b2", "bot-t2b1", "bot-t2b2"].map(String::from)
+    }
+
+    fn outcome(stdout: &str, stderr: &str) -> GameOutcome {
+        let (events, skipped) = parse_events(&lines(stdout), &lines(stderr));
+        assert!(skipped.is_empty(), "skipped {:?}", skipped);
+        game_outcome(&events, &bot_ids())
+    }
+
+    #[test]
+    fn team1_wins() {
+        let stdout = lines(fixture!("team1_wins.stdout"));
+        let (events, skipped) = parse_events(&stdout, &[]);
+        assert!(skipped.is_empty());
+        assert_eq!(events.iter().filter(|event| matches!(event, GameEvent::Score { .. })).count(), 8);
+        assert_eq!(events[4], GameEvent::Score { color: Color::Yellow, score: 41 });
+
+        let GameOutcome::Played { survived, winner, stats } = game_outcome(&events, &bot_ids()) else {
+            panic!("the game was played");
+        };
+        assert_eq!(survived, [true, true, false, false]);
+        assert_eq!(winner, Some(1));
+
+        let yellow = stats[0].as_ref().unwrap();
+        assert_eq!(yellow.turns_played, 214);
+        assert_eq!(yellow.planets_conquered, 9);
+        assert_eq!(yellow.total_troops_generated, 812);
+        // the blocks are printed yellow, blue, green, cyan
+        assert_eq!(stats[2].as_ref().unwrap().turns_played, 180);
+        assert_eq!(stats[1].as_ref().unwrap().fleet_generated, 25);
+        assert_eq!(stats[3].as_ref().unwrap().turns_played, 201);
+    }
+
+    #[test]
+    fn timeout_is_decided_by_score() {
+        let GameOutcome::Played { survived, winner, .. } = outcome(fixture!("timeout_by_score.stdout"), "") else {
+            panic!("the game was played");
+        };
+        assert_eq!(survived, [true; 4]);
+        // yellow and green 35 + 31, blue and cyan 52 + 9
+        assert_eq!(winner, Some(1));
+    }
+
+    #[test]
+    fn equal_scores_are_a_draw() {
+        let GameOutcome::Played { survived, winner, .. } = outcome(fixture!("timeout_draw.stdout"), "") else {
+            panic!("the game was played");
+        };
+        assert_eq!(survived, [true; 4]);
+        assert_eq!(winner, None);
+    }
+
+    #[test]
+    fn stats_header_naming_a_bot() {
+        let stdout = lines("STAT: bot-t2b2\nsurvive: true\nSTAT: bot-t1b1\nsurvive: false");
+        let GameOutcome::Played { survived, winner, .. } = game_outcome(&parse_events(&stdout, &[]).0, &bot_ids()) else {
+            panic!("the game was played");
+        };
+        assert_eq!(survived, [false, false, false, true]);
+        assert_eq!(winner, Some(2));
+    }
+
+    #[test]
+    fn crash_blames_the_bot_in_stderr() {
+        let outcome = outcome(fixture!("crash.stdout"), fixture!("crash.stderr"));
+        let GameOutcome::Bugged { blame, error } = outcome else {
+            panic!("the game is bugged");
+        };
+        assert_eq!(blame, Some(2));
+        assert!(error.starts_with("Game finished with errors\n"));
+    }
+
+    #[test]
+    fn single_stderr_line_is_not_a_crash() {
+        let outcome = outcome(fixture!("team1_wins.stdout"), "Game finished");
+        assert!(matches!(outcome, GameOutcome::Played { winner: Some(1), .. }));
+    }
+
+    #[test]
+    fn loss_without_stats_blames_the_bot() {
+        let outcome = outcome(fixture!("no_stats_loss.stdout"), "");
+        assert_eq!(outcome, GameOutcome::Bugged {
+            blame: Some(1),
+            error: "L bot-t1b2 did not respond in time".to_string(),
+        });
+    }
+
+    #[test]
+    fn unknown_stat_is_skipped() {
+        let (events, skipped) = parse_events(&lines(fixture!("unknown_stat.stdout")), &[]);
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].line, 4);
+        assert_eq!(skipped[0].content, "planetsHeld: 4");
+        assert_eq!(skipped[0].reason, "unknown statistic planetsHeld");
+
+        // the known statistics of the block are kept
+        let stats = events.iter().find_map(|event| match event {
+            GameEvent::Stats { stats, .. } => Some(stats),
+            _ => None,
+        });
+        assert_eq!(stats.unwrap().turns_played, 120);
+        assert_eq!(events.len(), 2);
+    }
+
+    #[test]
+    fn replay_frames() {
+        let replay = parse_replay(&lines(fixture!("replay.stdout")), None).unwrap();
+        assert!(!replay.truncated);
+        let frames = replay.frames;
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames.iter().map(|f| f.turn).collect::<Vec<usize>>(), vec![0, 1, 2]);
+
+        assert_eq!(frames[0].planets.len(), 3);
+        assert_eq!(frames[0].planets[2], ReplayPlanet { x: 10.0, y: 7.5, size: 1, troops: 5, owner: None });
+        assert!(frames[0].fleets.is_empty());
+        assert_eq!(frames[0].scores[&Color::Yellow], 20);
+
+        assert_eq!(frames[1].fleets, vec![ReplayFleet {
+            troops: 12, owner: Some(Color::Yellow), from: 0, to: 2, total_turns: 3, turns_left: 2,
+        }]);
+        assert_eq!(frames[2].planets[2].owner, Some(Color::Yellow));
+        // scores are carried over to the turns without updates
+        assert_eq!(frames[2].scores[&Color::Blue], 18);
+        assert_eq!(frames[2].scores[&Color::Yellow], 25);
+    }
+
+    #[test]
+    fn replay_quiet_turns() {
+        // the first and the third turn have neither fleets nor score updates
+        let frames = parse_replay(&lines(fixture!("replay_quiet.stdout")), None).unwrap().frames;
+        assert_eq!(frames.len(), 4);
+        assert!(frames.iter().all(|f| f.planets.len() == 3));
+        assert_eq!(frames.iter().map(|f| f.planets[0].troops).collect::<Vec<i32>>(), vec![20, 21, 10, 11]);
+        assert!(frames[0].scores.is_empty());
+        assert_eq!(frames[1].fleets.len(), 1);
+        assert!(frames[2].fleets.is_empty());
+        assert_eq!(frames[2].scores[&Color::Yellow], 20);
+        assert_eq!(frames[3].fleets.len(), 1);
+    }
+
+    #[test]
+    fn replay_stops_after_the_last_turn() {
+        let stdout = lines(fixture!("replay_quiet.stdout"));
+        let all = parse_replay(&stdout, None).unwrap().frames;
+
+        let replay = parse_replay(&stdout, Some(1)).unwrap();
+        assert!(replay.truncated);
+        assert_eq!(replay.frames, all[..2]);
+
+        // a malformed turn after the last one is not read
+        let mut broken = stdout.clone();
+        broken.push("P 1.0 2.0 1 five yellow".to_string());
+        assert!(parse_replay(&broken, Some(2)).unwrap().truncated);
+
+        // the output ends with the last turn
+        let replay = parse_replay(&stdout, Some(3)).unwrap();
+        assert!(!replay.truncated);
+        assert_eq!(replay.frames, all);
+    }
+
+    #[test]
+    fn malformed_planet_is_an_error() {
+        let error = parse_replay(&lines("P 1.0 2.0 1 five yellow"), None).unwrap_err();
+        assert_eq!(error.line, 1);
+        assert_eq!(error.reason, "expected an integer");
+    }
+
+    #[test]
+    fn malformed_score_is_skipped() {
+        let (events, skipped) = parse_events(&lines(fixture!("bad_score.stdout")), &[]);
+        assert_eq!(events, vec![GameEvent::Score { color: Color::Yellow, score: 20 }]);
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].line, 2);
+        assert_eq!(skipped[0].reason, "the score is not a number");
+    }
+}
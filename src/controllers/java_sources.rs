@@ -0,0 +1,330 @@
+use std::{fs, io, path::{Path, PathBuf}};
+
+use crate::models::{bot::CompileDiagnostic, errors::MatchMakerError};
+
+/// The class the evaluator starts (`java Player`) in the directory of a bot.
+pub const PLAYER_CLASS: &str = "Player";
+
+/// A class found in the sources of a bot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JavaClass {
+    /// Fully qualified name, e.g. `com.team.Player`.
+    pub name: String,
+    /// Whether the class declares a `main` method.
+    pub has_main: bool,
+}
+
+impl JavaClass {
+    /// Returns the name of the class without its package.
+    pub fn simple_name(&self) -> &str {
+        self.name.rsplit('.').next().unwrap_or(&self.name)
+    }
+
+    /// Returns whether the class is in the default package.
+    pub fn in_default_package(&self) -> bool {
+        !self.name.contains('.')
+    }
+}
+
+/// Returns whether an entry of an uploaded zip is ignored, e.g. the metadata macOS adds to zips.
+fn is_ignored(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.eq("__MACOSX") || name.starts_with('.'))
+}
+
+/// Moves the contents of the only folder in `directory` up into `directory`.
+///
+/// Students often zip their project folder instead of its contents. Nothing happens if
+/// `directory` holds anything besides a single folder (ignored entries left aside).
+pub fn flatten_single_folder(directory: &Path) -> io::Result<()> {
+    let entries: Vec<PathBuf> = fs::read_dir(directory)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| !is_ignored(path))
+        .collect();
+    if entries.len() != 1 || !entries[0].is_dir() {
+        return Ok(());
+    }
+
+    // the folder may contain an entry with its own name, so it is moved out of the way first
+    let folder = directory.join(".single-folder");
+    fs::rename(&entries[0], &folder)?;
+    for entry in fs::read_dir(&folder)? {
+        let entry = entry?;
+        fs::rename(entry.path(), directory.join(entry.file_name()))?;
+    }
+    fs::remove_dir(&folder)
+}
+
+/// Returns the Java source files in `directory` and all of its sub-directories, sorted.
+pub fn find_java_sources(directory: &Path) -> io::Result<Vec<PathBuf>> {
+    find_sources(directory, &["java"])
+}
+
+/// Returns the files with one of the given extensions in `directory` and all of its
+/// sub-directories, sorted.
+pub fn find_sources(directory: &Path, extensions: &[&str]) -> io::Result<Vec<PathBuf>> {
+    let mut sources = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        if is_ignored(&path) {
+            continue;
+        }
+        if path.is_dir() {
+            sources.extend(find_sources(&path, extensions)?);
+        } else if path.extension().and_then(|extension| extension.to_str()).is_some_and(|extension| extensions.contains(&extension)) {
+            sources.push(path);
+        }
+    }
+    sources.sort();
+    Ok(sources)
+}
+
+/// Reads the class declared by a source file, named after the file and placed in the file's
+/// `package`.
+pub fn read_java_class(path: &Path) -> io::Result<JavaClass> {
+    let content = fs::read_to_string(path)?;
+    let simple_name = path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default();
+
+    let package = content
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with("package "))
+        .map(|line| line.trim_start_matches("package ").trim_end_matches(';').trim().to_string());
+    let name = match package {
+        Some(package) => format!("{}.{}", package, simple_name),
+        None => simple_name.to_string(),
+    };
+
+    Ok(JavaClass { name, has_main: content.contains("public static void main(") })
+}
+
+/// Returns the `Main-Class` of the `META-INF/MANIFEST.MF` in `directory`, if there is one.
+pub fn manifest_main_class(directory: &Path) -> Option<String> {
+    let manifest = fs::read_to_string(directory.join("META-INF").join("MANIFEST.MF")).ok()?;
+    manifest
+        .lines()
+        .find_map(|line| line.strip_prefix("Main-Class:"))
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// Finds the class a bot is started with.
+///
+/// That's the class named in the `Main-Class` of a `META-INF/MANIFEST.MF` if the bot has one,
+/// `Player` otherwise. Both may be in any package, a name without package matches a class of
+/// that name in any package. A class in the default package is preferred.
+///
+/// # Errors
+///
+/// Returns a `MatchMakerError::PlayerFileMissing` if there is no such class, or a
+/// `MatchMakerError::MainMethodNotInPlayerFile` if it has no `main` method.
+///
+pub fn find_main_class(classes: &[JavaClass], configured: Option<&str>) -> Result<JavaClass, MatchMakerError> {
+    let wanted = configured.unwrap_or(PLAYER_CLASS);
+    let mut candidates: Vec<&JavaClass> = classes
+        .iter()
+        .filter(|class| class.name.eq(wanted) || (!wanted.contains('.') && class.simple_name().eq(wanted)))
+        .collect();
+    candidates.sort_by_key(|class| !class.in_default_package());
+
+    match candidates.first() {
+        None => Err(MatchMakerError::PlayerFileMissing),
+        Some(class) if !class.has_main => Err(MatchMakerError::MainMethodNotInPlayerFile),
+        Some(class) => Ok((*class).clone()),
+    }
+}
+
+/// Returns the source of a `Player` class in the default package starting `main_class`, or
+/// `None` if `main_class` is that class already.
+pub fn player_shim(main_class: &JavaClass) -> Option<String> {
+    if main_class.name.eq(PLAYER_CLASS) {
+        return None;
+    }
+    Some(format!(
+        "public class {} {{\n    public static void main(String[] args) throws Exception {{\n        {}.main(args);\n    }}\n}}\n",
+        PLAYER_CLASS,
+        main_class.name
+    ))
+}
+
+/// Parses the errors in the output of `javac`, which reports them as
+/// `{file}:{line}: error: {message}`, followed by the offending source line and a caret.
+///
+/// Files are made relative to `directory`, the directory the bot was compiled in.
+pub fn parse_javac_diagnostics(output: &str, directory: &Path) -> Vec<CompileDiagnostic> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (location, message) = line.split_once(": error: ")?;
+            let (file, line) = location.rsplit_once(':')?;
+            let file = Path::new(file);
+            Some(CompileDiagnostic {
+                file: file.strip_prefix(directory).unwrap_or(file).to_string_lossy().to_string(),
+                line: line.parse().ok()?,
+                message: message.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::{Path, PathBuf}};
+
+    use uuid::Uuid;
+
+    use crate::models::errors::MatchMakerError;
+
+    use super::*;
+
+    const PLAYER: &str = "public class Player {\n    public static void main(String[] args) {}\n}\n";
+
+    /// Creates an empty directory with the given files, paths are relative to it.
+    fn layout(files: &[(&str, &str)]) -> PathBuf {
+        let directory = env::temp_dir().join(format!("java_sources_{}", Uuid::new_v4()));
+        for (path, content) in files {
+            let path = directory.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    fn relative(directory: &Path, paths: Vec<PathBuf>) -> Vec<String> {
+        paths
+            .into_iter()
+            .map(|path| path.strip_prefix(directory).unwrap().to_string_lossy().to_string())
+            .collect()
+    }
+
+    fn main_class(directory: &Path) -> Result<JavaClass, MatchMakerError> {
+        let classes: Vec<JavaClass> = find_java_sources(directory)
+            .unwrap()
+            .iter()
+            .map(|path| read_java_class(path).unwrap())
+            .collect();
+        find_main_class(&classes, manifest_main_class(directory).as_deref())
+    }
+
+    #[test]
+    fn flat_layout() {
+        let directory = layout(&[("Player.java", PLAYER), ("Helper.java", "class Helper {}")]);
+        flatten_single_folder(&directory).unwrap();
+
+        assert_eq!(relative(&directory, find_java_sources(&directory).unwrap()), vec!["Helper.java", "Player.java"]);
+        let main = main_class(&directory).unwrap();
+        assert_eq!(main.name, "Player");
+        assert_eq!(player_shim(&main), None);
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn single_top_level_folder() {
+        let directory = layout(&[
+            ("MyBot/Player.java", PLAYER),
+            ("MyBot/util/Helper.java", "class Helper {}"),
+            ("__MACOSX/MyBot/._Player.java", "garbage"),
+        ]);
+        flatten_single_folder(&directory).unwrap();
+
+        assert!(directory.join("Player.java").is_file());
+        assert_eq!(relative(&directory, find_java_sources(&directory).unwrap()), vec!["Player.java", "util/Helper.java"]);
+        assert_eq!(main_class(&directory).unwrap().name, "Player");
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn single_folder_containing_its_own_name() {
+        let directory = layout(&[("bot/bot/Player.java", PLAYER)]);
+        flatten_single_folder(&directory).unwrap();
+
+        assert_eq!(relative(&directory, find_java_sources(&directory).unwrap()), vec!["bot/Player.java"]);
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn multiple_top_level_entries_are_kept() {
+        let directory = layout(&[("src/Player.java", PLAYER), ("README.md", "# Bot")]);
+        flatten_single_folder(&directory).unwrap();
+
+        assert_eq!(relative(&directory, find_java_sources(&directory).unwrap()), vec!["src/Player.java"]);
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn player_in_package() {
+        let directory = layout(&[
+            ("src/com/team/Player.java", "package com.team;\n\npublic class Player {\n    public static void main(String[] args) {}\n}\n"),
+            ("src/com/team/map/Planet.java", "package com.team.map;\n\npublic class Planet {}\n"),
+        ]);
+
+        let main = main_class(&directory).unwrap();
+        assert_eq!(main.name, "com.team.Player");
+        assert!(player_shim(&main).unwrap().contains("com.team.Player.main(args);"));
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn main_class_from_manifest() {
+        let directory = layout(&[
+            ("META-INF/MANIFEST.MF", "Manifest-Version: 1.0\nMain-Class: bot.Main\n"),
+            ("bot/Main.java", "package bot;\n\npublic class Main {\n    public static void main(String[] args) {}\n}\n"),
+            ("bot/Player.java", "package bot;\n\nclass Player {}\n"),
+        ]);
+
+        let main = main_class(&directory).unwrap();
+        assert_eq!(main.name, "bot.Main");
+        assert!(player_shim(&main).unwrap().contains("bot.Main.main(args);"));
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn default_package_is_preferred() {
+        let classes = vec![
+            JavaClass { name: "old.Player".to_string(), has_main: true },
+            JavaClass { name: "Player".to_string(), has_main: true },
+        ];
+        assert_eq!(find_main_class(&classes, None).unwrap().name, "Player");
+    }
+
+    #[test]
+    fn missing_player() {
+        let directory = layout(&[("Bot.java", "public class Bot {}")]);
+        assert!(matches!(main_class(&directory), Err(MatchMakerError::PlayerFileMissing)));
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn player_without_main() {
+        let directory = layout(&[("Player.java", "public class Player {}")]);
+        assert!(matches!(main_class(&directory), Err(MatchMakerError::MainMethodNotInPlayerFile)));
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn javac_diagnostics() {
+        let directory = Path::new("./resources/workdir/artifacts/abc.tmp");
+        let output = "Command executed with non-zero exit status: exit status: 1\n\
+            ./resources/workdir/artifacts/abc.tmp/Player.java:3: error: ';' expected\n\
+            \x20       int x = 1\n\
+            \x20                ^\n\
+            ./resources/workdir/artifacts/abc.tmp/com/team/Map.java:12: error: cannot find symbol\n\
+            \x20 symbol:   variable planets\n\
+            2 errors";
+
+        assert_eq!(parse_javac_diagnostics(output, directory), vec![
+            CompileDiagnostic { file: "Player.java".to_string(), line: 3, message: "';' expected".to_string() },
+            CompileDiagnostic { file: "com/team/Map.java".to_string(), line: 12, message: "cannot find symbol".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn javac_diagnostics_without_errors() {
+        assert!(parse_javac_diagnostics("Note: Player.java uses unchecked or unsafe operations.", Path::new(".")).is_empty());
+    }
+}
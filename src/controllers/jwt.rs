@@ -1,10 +1,11 @@
 use std::env;
 
+use actix_web::http::StatusCode;
 use actix_web_httpauth::extractors::bearer::BearerAuth;
 use serde::{Deserialize, Serialize};
 use jsonwebtoken::{decode, DecodingKey, Validation, Algorithm, encode, Header, EncodingKey, errors::Error};
 
-use crate::{models::user::User, db::operations_users::get_user_by_username};
+use crate::{models::{game_2v2::Game2v2, user::{Role, User}}, db::{operations_users::get_user_by_username, operations_teams::get_team_by_student_for_competition}};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
@@ -51,4 +52,31 @@ pub fn exchange_token_for_user(token: BearerAuth) -> Option<User> {
             None
         }
     }
-}
\ No newline at end of file
+}
+
+/// Checks that the requesting user may see a game: anyone may see public games, admins and the
+/// members of the two teams that played it all others.
+///
+/// # Errors
+///
+/// Returns the status to answer with instead, `403 Forbidden` without a valid token or for
+/// another team, `401 Unauthorized` if the user has no team in the game's competition.
+pub fn authorize_game_access(auth: Option<BearerAuth>, game: &Game2v2) -> Result<(), StatusCode> {
+    if game.public {
+        return Ok(());
+    }
+
+    let requesting_user = auth
+        .and_then(exchange_token_for_user)
+        .ok_or(StatusCode::FORBIDDEN)?;
+    if requesting_user.role == Role::Admin {
+        return Ok(());
+    }
+
+    let team = get_team_by_student_for_competition(requesting_user, game.competition_id.clone())
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+    if !team.id.eq(&game.team1_id) && !team.id.eq(&game.team2_id) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(())
+}
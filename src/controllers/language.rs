@@ -0,0 +1,397 @@
+use std::{fs, io, path::{Component, Path, PathBuf}, time::Duration};
+
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::models::{competition::SandboxLimits, errors::MatchMakerError};
+
+use super::{
+    command_executor::execute_command,
+    java_sources::{JavaClass, PLAYER_CLASS, find_java_sources, find_main_class, find_sources, manifest_main_class, player_shim, read_java_class},
+    sandbox::Sandbox
+};
+
+/// Names of the supported languages, in the order they are detected in.
+pub const LANGUAGES: [&str; 3] = ["java", "python", "cpp"];
+
+/// The time a compiler may take before it is killed, e.g. one reading `#include "/dev/zero"`.
+const COMPILE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// CPU time of a compiler in seconds, its other limits are the default limits of a game.
+const COMPILE_CPU_SECONDS: u64 = 60;
+
+/// The optional `bot.json` at the root of an uploaded bot.
+#[derive(Debug, Deserialize, Default)]
+pub struct BotManifest {
+    /// The language of the bot, detected from its sources if not set.
+    pub language: Option<String>,
+    /// The entrypoint of the bot: its main class for Java, its script for Python.
+    pub entrypoint: Option<String>,
+}
+
+/// A language bots can be written in.
+///
+/// The evaluator starts every bot with `java Player` in the bot's directory. Bots of other
+/// languages get a `Player` class that runs their `run_command`, see `write_launcher`.
+pub trait LanguageRuntime {
+    /// Returns whether the unzipped sources in `directory` look like a bot in this language.
+    fn detect(&self, directory: &Path) -> bool;
+
+    /// Finds the entrypoint of the bot in `directory`, checking that it can start the bot.
+    fn entrypoint(&self, directory: &Path, manifest: &BotManifest) -> Result<String, MatchMakerError>;
+
+    /// Compiles the bot in `directory`, leaving everything it needs to run in there. The
+    /// compiler runs in a sandbox, see `run_compiler`.
+    fn compile(&self, directory: &Path, entrypoint: &str) -> Result<(), MatchMakerError>;
+
+    /// The command starting the bot, run in its directory.
+    fn run_command(&self, entrypoint: &str) -> Vec<String>;
+}
+
+/// Bots in Java, started with a `Player` class in the default package, or the class set as
+/// `Main-Class` in a `META-INF/MANIFEST.MF` (or as entrypoint in `bot.json`).
+pub struct JavaRuntime;
+
+/// Bots in Python 3, started with `player.py` (or the entrypoint in `bot.json`).
+pub struct PythonRuntime;
+
+/// Bots in C++, compiled with `g++` into a single binary.
+pub struct CppRuntime;
+
+impl LanguageRuntime for JavaRuntime {
+    fn detect(&self, directory: &Path) -> bool {
+        find_java_sources(directory).is_ok_and(|sources| !sources.is_empty())
+    }
+
+    fn entrypoint(&self, directory: &Path, manifest: &BotManifest) -> Result<String, MatchMakerError> {
+        let classes = find_java_sources(directory)
+            .map_err(MatchMakerError::IOError)?
+            .iter()
+            .map(|file| read_java_class(file))
+            .collect::<io::Result<Vec<JavaClass>>>()
+            .map_err(MatchMakerError::IOError)?;
+        let configured = manifest.entrypoint.clone().or(manifest_main_class(directory));
+        let main_class = find_main_class(&classes, configured.as_deref())?;
+
+        if !main_class.name.eq(PLAYER_CLASS) && classes.iter().any(|class| class.name.eq(PLAYER_CLASS)) {
+            return Err(MatchMakerError::CompileError(format!(
+                "{} in the default package is in the way of starting the main class {}",
+                PLAYER_CLASS,
+                main_class.name
+            )));
+        }
+        Ok(main_class.name)
+    }
+
+    fn compile(&self, directory: &Path, entrypoint: &str) -> Result<(), MatchMakerError> {
+        let mut java_files = find_java_sources(directory).map_err(MatchMakerError::IOError)?;
+        let main_class = JavaClass { name: entrypoint.to_string(), has_main: true };
+        if let Some(shim) = player_shim(&main_class) {
+            let shim_path = directory.join(format!("{}.java", PLAYER_CLASS));
+            fs::write(&shim_path, shim).map_err(MatchMakerError::IOError)?;
+            java_files.push(shim_path);
+        }
+        javac(directory, &java_files)
+    }
+
+    fn run_command(&self, _entrypoint: &str) -> Vec<String> {
+        // packaged main classes are started by the `Player` shim
+        vec!["java".to_string(), PLAYER_CLASS.to_string()]
+    }
+}
+
+impl LanguageRuntime for PythonRuntime {
+    fn detect(&self, directory: &Path) -> bool {
+        find_sources(directory, &["py"]).is_ok_and(|sources| !sources.is_empty())
+    }
+
+    fn entrypoint(&self, directory: &Path, manifest: &BotManifest) -> Result<String, MatchMakerError> {
+        let entrypoint = manifest.entrypoint.clone().unwrap_or("player.py".to_string());
+        if !directory.join(&entrypoint).is_file() {
+            return Err(MatchMakerError::PlayerFileMissing);
+        }
+        Ok(entrypoint)
+    }
+
+    fn compile(&self, directory: &Path, _entrypoint: &str) -> Result<(), MatchMakerError> {
+        // nothing to compile, but syntax errors are found at upload instead of in every game
+        let python_files = source_strings(directory, &find_sources(directory, &["py"]).map_err(MatchMakerError::IOError)?);
+        let mut args = vec!["-m", "py_compile"];
+        args.extend(python_files.iter().map(String::as_str));
+        run_compiler(directory, "python3", args)
+    }
+
+    fn run_command(&self, entrypoint: &str) -> Vec<String> {
+        // unbuffered, the evaluator waits for every line
+        vec!["python3".to_string(), "-u".to_string(), entrypoint.to_string()]
+    }
+}
+
+impl LanguageRuntime for CppRuntime {
+    fn detect(&self, directory: &Path) -> bool {
+        find_sources(directory, &["cpp", "cc"]).is_ok_and(|sources| !sources.is_empty())
+    }
+
+    fn entrypoint(&self, directory: &Path, _manifest: &BotManifest) -> Result<String, MatchMakerError> {
+        let has_main = find_sources(directory, &["cpp", "cc"])
+            .map_err(MatchMakerError::IOError)?
+            .iter()
+            .any(|file| fs::read_to_string(file).is_ok_and(|content| content.contains("int main(")));
+        if !has_main {
+            return Err(MatchMakerError::MainMethodNotInPlayerFile);
+        }
+        Ok("player".to_string())
+    }
+
+    fn compile(&self, directory: &Path, entrypoint: &str) -> Result<(), MatchMakerError> {
+        let sources = source_strings(directory, &find_sources(directory, &["cpp", "cc"]).map_err(MatchMakerError::IOError)?);
+        let mut args = vec!["-O2", "-std=c++17", "-o", entrypoint];
+        args.extend(sources.iter().map(String::as_str));
+        run_compiler(directory, "g++", args)
+    }
+
+    fn run_command(&self, entrypoint: &str) -> Vec<String> {
+        vec![format!("./{}", entrypoint)]
+    }
+}
+
+/// Returns the runtime of the language with the given name, `None` for unknown names.
+///
+/// Known languages are `java`, `python` and `cpp`.
+pub fn language_runtime(name: &str) -> Option<Box<dyn LanguageRuntime>> {
+    match name {
+        "java" => Some(Box::new(JavaRuntime)),
+        "python" => Some(Box::new(PythonRuntime)),
+        "cpp" => Some(Box::new(CppRuntime)),
+        _ => None,
+    }
+}
+
+/// Reads the `bot.json` of an unzipped bot, bots without one get the default manifest.
+///
+/// # Errors
+///
+/// Returns a `MatchMakerError::CompileError` if the manifest is not valid JSON.
+///
+pub fn read_manifest(directory: &Path) -> Result<BotManifest, MatchMakerError> {
+    match fs::read_to_string(directory.join("bot.json")) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| MatchMakerError::CompileError(format!("Invalid bot.json: {}", e))),
+        Err(_) => Ok(BotManifest::default()),
+    }
+}
+
+/// Chooses the runtime of an unzipped bot.
+///
+/// The language set in the bot's manifest wins over the competition's language. If neither
+/// is set, the language is detected from the sources.
+///
+/// # Arguments
+///
+/// * `directory` - The directory of the unzipped bot.
+/// * `manifest` - The manifest of the bot.
+/// * `competition_language` - The language of the competition, empty to detect it.
+///
+/// # Errors
+///
+/// Returns a `MatchMakerError::CompileError` if the language is unknown or cannot be detected.
+///
+pub fn select_runtime(directory: &Path, manifest: &BotManifest, competition_language: &str) -> Result<Box<dyn LanguageRuntime>, MatchMakerError> {
+    let configured = manifest.language.clone().unwrap_or(competition_language.to_string());
+    if !configured.is_empty() {
+        return language_runtime(&configured)
+            .ok_or(MatchMakerError::CompileError(format!("Unknown language {}", configured)));
+    }
+
+    LANGUAGES
+        .iter()
+        .filter_map(|name| language_runtime(name))
+        .find(|runtime| runtime.detect(directory))
+        .ok_or(MatchMakerError::CompileError(format!("No sources found, supported languages are {}", LANGUAGES.join(", "))))
+}
+
+/// Adds a `Player` class to the bot in `directory` that runs `command` in the bot's directory,
+/// passing the standard streams through. The evaluator can then start the bot like a Java bot.
+///
+/// The bot's directory is the one `Player.class` is loaded from, parts of the command starting
+/// with `./` are resolved against it.
+pub fn write_launcher(directory: &Path, command: &[String]) -> Result<(), MatchMakerError> {
+    let arguments: Vec<String> = command
+        .iter()
+        .map(|part| format!("\"{}\"", part.replace('\\', "\\\\").replace('"', "\\\"")))
+        .collect();
+    let launcher = format!(
+        concat!(
+            "import java.io.File;\n\n",
+            "public class {0} {{\n",
+            "    public static void main(String[] args) throws Exception {{\n",
+            "        File directory = new File({0}.class.getProtectionDomain().getCodeSource().getLocation().toURI());\n",
+            "        String[] command = {{{1}}};\n",
+            "        for (int i = 0; i < command.length; i++) {{\n",
+            "            if (command[i].startsWith(\"./\")) {{\n",
+            "                command[i] = new File(directory, command[i]).getPath();\n",
+            "            }}\n",
+            "        }}\n",
+            "        Process process = new ProcessBuilder(command).directory(directory).inheritIO().start();\n",
+            "        System.exit(process.waitFor());\n",
+            "    }}\n",
+            "}}\n"
+        ),
+        PLAYER_CLASS,
+        arguments.join(", ")
+    );
+
+    let launcher_path = directory.join(format!("{}.java", PLAYER_CLASS));
+    fs::write(&launcher_path, launcher).map_err(MatchMakerError::IOError)?;
+    javac(directory, &[launcher_path])
+}
+
+/// Returns the paths of source files relative to `directory`, the compiler's working directory.
+fn source_strings(directory: &Path, sources: &[PathBuf]) -> Vec<String> {
+    sources
+        .iter()
+        .map(|file| file.strip_prefix(directory).unwrap_or(file).to_string_lossy().to_string())
+        .collect()
+}
+
+/// Compiles Java files, the class files go to `directory` (by package).
+fn javac(directory: &Path, java_files: &[PathBuf]) -> Result<(), MatchMakerError> {
+    let java_files = source_strings(directory, java_files);
+    let mut args = vec!["-d", "."];
+    args.extend(java_files.iter().map(String::as_str));
+    run_compiler(directory, "javac", args)
+}
+
+/// Runs a compiler in `directory`, inside a sandbox (see `Sandbox`). The bot's directory is
+/// the only directory of the host the compiler sees besides the runtimes, it has no network
+/// and none of the dashboard's environment, and it is killed after `COMPILE_TIMEOUT`.
+///
+/// A failing compiler is a `MatchMakerError::CompileError` holding its errors (without the ones
+/// about files outside the bot, see `redact_foreign_files`), and so is a compiler running out
+/// of time. A compiler that cannot be run (e.g. it is not installed) is a
+/// `MatchMakerError::IOError`, and a sandbox that cannot be set up a `MatchMakerError::SandboxError`.
+fn run_compiler(directory: &Path, compiler: &str, args: Vec<&str>) -> Result<(), MatchMakerError> {
+    let limits = SandboxLimits { cpu_seconds: COMPILE_CPU_SECONDS, ..SandboxLimits::default() };
+    let sandbox = Sandbox::new(directory, &[], &format!("compile-{}", Uuid::new_v4()), limits)?;
+    let args: Vec<String> = args.into_iter().map(String::from).collect();
+    match execute_command(sandbox.command(compiler, &args), COMPILE_TIMEOUT) {
+        Ok(_) => Ok(()),
+        // a non-zero exit status is reported as `Other`
+        Err(e) if e.kind() == io::ErrorKind::Other => Err(MatchMakerError::CompileError(redact_foreign_files(&e.to_string()))),
+        Err(e) if e.kind() == io::ErrorKind::TimedOut => Err(MatchMakerError::CompileError(format!(
+            "Compilation took longer than {} seconds",
+            COMPILE_TIMEOUT.as_secs()
+        ))),
+        Err(e) => Err(MatchMakerError::IOError(e)),
+    }
+}
+
+/// Removes the messages about files outside the bot's directory from the output of a compiler,
+/// along with the source lines they quote. The compiler runs in the bot's directory, so its own
+/// files are reported by relative paths. Anything else, e.g. a bot including `/proc/self/environ`
+/// or a system header, is left out instead of being shown to the team.
+fn redact_foreign_files(output: &str) -> String {
+    let mut kept: Vec<&str> = Vec::new();
+    let mut redacting = false;
+    for line in output.lines() {
+        match reported_file(line) {
+            Some(file) => redacting = !is_inside_bot(file),
+            // compilers indent the lines they quote below a message
+            None if line.starts_with(char::is_whitespace) => {},
+            None => redacting = false,
+        }
+        if !redacting {
+            kept.push(line);
+        }
+    }
+    kept.join("\n")
+}
+
+/// Returns the file a line of compiler output is about, e.g. `player.cpp` of
+/// `player.cpp:3:5: error: ...` or of `In file included from player.cpp:1:`.
+fn reported_file(line: &str) -> Option<&str> {
+    let location = line.trim_start();
+    let location = location
+        .strip_prefix("In file included from ")
+        .or(location.strip_prefix("from "))
+        .unwrap_or(location);
+    let (file, rest) = location.split_once(':')?;
+    let about_file = rest.starts_with(|c: char| c.is_ascii_digit()) || rest.starts_with(" In ") || rest.starts_with(" At ");
+    if file.is_empty() || file.contains(char::is_whitespace) || !about_file {
+        return None;
+    }
+    Some(file)
+}
+
+/// Returns whether a path reported by a compiler running in the bot's directory is in there.
+fn is_inside_bot(file: &str) -> bool {
+    let mut depth = 0;
+    for component in Path::new(file).components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {},
+            Component::ParentDir if depth > 0 => depth -= 1,
+            _ => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_included_host_files() {
+        let output = concat!(
+            "Command executed with non-zero exit status: exit status: 1\n",
+            "In file included from player.cpp:1:\n",
+            "/proc/self/environ:1:1: error: 'DATABASE_URL' does not name a type\n",
+            "    1 | DATABASE_URL=mysql://dashboard:secret@db/dashboard\n",
+            "      | ^~~~~~~~~~~~\n",
+            "player.cpp:4:5: error: 'foo' was not declared in this scope\n",
+            "    4 |     foo();\n",
+            "      |     ^~~\n",
+            "compilation terminated.",
+        );
+        let redacted = redact_foreign_files(output);
+        assert!(!redacted.contains("DATABASE_URL"));
+        assert!(!redacted.contains("secret"));
+        assert_eq!(redacted, concat!(
+            "Command executed with non-zero exit status: exit status: 1\n",
+            "In file included from player.cpp:1:\n",
+            "player.cpp:4:5: error: 'foo' was not declared in this scope\n",
+            "    4 |     foo();\n",
+            "      |     ^~~\n",
+            "compilation terminated.",
+        ));
+    }
+
+    #[test]
+    fn redacts_files_above_the_bot() {
+        let output = concat!(
+            "In file included from src/player.cpp:2:\n",
+            "src/../../../.env:1:1: error: 'JWT_SECRET' does not name a type\n",
+            "    1 | JWT_SECRET=secret\n",
+            "/usr/include/c++/12/bits/stl_vector.h: In function 'void f()':\n",
+            "    5 | secret\n",
+            "src/player.cpp:2:10: error: expected ';'",
+        );
+        assert_eq!(redact_foreign_files(output), concat!(
+            "In file included from src/player.cpp:2:\n",
+            "src/player.cpp:2:10: error: expected ';'",
+        ));
+    }
+
+    #[test]
+    fn keeps_messages_about_the_bot() {
+        let output = concat!(
+            "Command executed with non-zero exit status: exit status: 1\n",
+            "bot/Player.java:3: error: ';' expected\n",
+            "        int x = 1\n",
+            "                 ^\n",
+            "1 error",
+        );
+        assert_eq!(redact_foreign_files(output), output);
+    }
+}
@@ -0,0 +1,154 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use diesel::result::Error;
+
+use crate::{
+    db::{
+        operations_teams::get_teams_by_competition_id,
+        operations_game2v2::get_games_by_competition_id,
+        operations_leaderboard::insert_snapshots
+    },
+    models::{
+        competition::Competition,
+        leaderboard::{LeaderboardEntry, NewLeaderboardSnapshot},
+        team::Team,
+        game_2v2::Game2v2
+    }
+};
+
+#[derive(Debug, Default)]
+struct TeamRecord {
+    wins: i32,
+    losses: i32,
+    games_played: i32,
+    bots_survived: i32,
+    elo_trend: i32,
+}
+
+/// Builds the leaderboard of a competition.
+///
+/// Teams are ranked by their current ELO (teams with equal ELO share a rank). The record of
+/// each team (wins, losses, win and survival rates) is computed from all the games the team
+/// played in the competition, while the ELO trend only sums the ELO changes of the games
+/// played in the last `trend_rounds` rounds.
+///
+/// # Arguments
+///
+/// * `competition` - The competition for which the leaderboard is built.
+/// * `trend_rounds` - The number of most recent rounds to include in the ELO trend.
+///
+/// # Returns
+///
+/// A `Result` containing the ranked leaderboard entries, or a diesel `Error` if the teams
+/// or games could not be fetched.
+///
+pub fn build_leaderboard(competition: &Competition, trend_rounds: i32) -> Result<Vec<LeaderboardEntry>, Error> {
+    let teams = get_teams_by_competition_id(competition.id.clone())?;
+    let games = get_games_by_competition_id(competition.id.clone())?;
+    let trend_start = competition.round - trend_rounds;
+    Ok(rank_teams(teams, games, trend_start))
+}
+
+/// Stores the current standings of a competition as the snapshot of its current round.
+///
+/// Meant to be called at the end of a round, once the ELO changes of the round have been
+/// applied, so the standings can later be looked up per round or per team.
+///
+/// # Arguments
+///
+/// * `competition` - The competition whose standings are recorded.
+///
+/// # Returns
+///
+/// An empty `Result`, or a diesel `Error` if the standings could not be built or stored.
+///
+pub fn snapshot_leaderboard(competition: &Competition) -> Result<(), Error> {
+    let snapshots = build_leaderboard(competition, 0)?
+        .into_iter()
+        .map(|entry| NewLeaderboardSnapshot {
+            competition_id: competition.id.clone(),
+            round: competition.round,
+            entry,
+        })
+        .collect::<Vec<NewLeaderboardSnapshot>>();
+
+    if snapshots.is_empty() {
+        return Ok(());
+    }
+    insert_snapshots(snapshots)
+}
+
+fn rank_teams(mut teams: Vec<Team>, games: Vec<Game2v2>, trend_start: i32) -> Vec<LeaderboardEntry> {
+    let mut records: HashMap<String, TeamRecord> = HashMap::new();
+
+    for game in games.into_iter() {
+        // a team playing against itself tells nothing about its standing, neither does a void game
+        if game.team1_id == game.team2_id || game.void {
+            continue;
+        }
+        let in_trend = game.round >= trend_start;
+
+        let record = records.entry(game.team1_id.clone()).or_default();
+        record_game(
+            record,
+            game.winner_id == game.team1_id,
+            game.draw,
+            [game.team1bot1_survived, game.team1bot2_survived],
+            if in_trend { game.team1_elo } else { 0 }
+        );
+
+        let record = records.entry(game.team2_id.clone()).or_default();
+        record_game(
+            record,
+            game.winner_id == game.team2_id,
+            game.draw,
+            [game.team2bot1_survived, game.team2bot2_survived],
+            if in_trend { game.team2_elo } else { 0 }
+        );
+    }
+
+    teams.sort_by_key(|team| Reverse(team.elo));
+
+    let mut entries: Vec<LeaderboardEntry> = Vec::new();
+    for (index, team) in teams.into_iter().enumerate() {
+        // teams with the same ELO share the rank of the first one of them
+        let rank = match entries.last() {
+            Some(previous) if previous.elo == team.elo => previous.rank,
+            _ => index + 1,
+        };
+        let record = records.remove(&team.id).unwrap_or_default();
+        let (win_rate, survival_rate) = if record.games_played > 0 {
+            (
+                record.wins as f32 / record.games_played as f32,
+                record.bots_survived as f32 / (2 * record.games_played) as f32,
+            )
+        } else {
+            (0., 0.)
+        };
+
+        entries.push(LeaderboardEntry {
+            rank,
+            team_id: team.id,
+            team_name: team.name,
+            elo: team.elo,
+            wins: record.wins,
+            losses: record.losses,
+            games_played: record.games_played,
+            win_rate,
+            survival_rate,
+            elo_trend: record.elo_trend,
+        });
+    }
+    entries
+}
+
+fn record_game(record: &mut TeamRecord, won: bool, draw: bool, survived: [bool; 2], elo_change: i32) {
+    record.games_played += 1;
+    if won {
+        record.wins += 1;
+    } else if !draw {
+        record.losses += 1;
+    }
+    record.bots_survived += survived.iter().filter(|s| **s).count() as i32;
+    record.elo_trend += elo_change;
+}
@@ -1,36 +1,53 @@
-use std::{path::Path, fs::{self, File}, process::{Command, Stdio, ExitStatus, Output}, time::Duration, thread, io::{BufReader, BufRead, self}, collections::HashMap, sync::{Arc, Mutex}};
-use rand::Rng;
-use rayon::prelude::{IntoParallelIterator, ParallelIterator, IntoParallelRefIterator};
+use std::{path::{Path, PathBuf}, fs, process::{Stdio, ExitStatus}, time::Duration, thread, io::{BufReader, BufRead}, collections::HashMap, sync::{Arc, Mutex}};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 use wait_timeout::ChildExt;
+use zip::{ZipArchive, result::ZipError};
 use num_cpus;
+use once_cell::sync::Lazy;
 
 use crate::{
     db::{
         operations_competition::{get_competition_by_id, set_competition_round}, 
-        operations_teams::get_teams_by_competition_id, 
-        operations_bot::{get_bot_by_id, set_bot_error}, operations_game2v2::insert_game,
+        operations_teams::{get_teams_by_competition_id, get_team_by_id}, 
+        operations_bot::{get_bot_by_id, set_bot_compile_result}, 
+        operations_game2v2::{insert_game, get_game_by_id, get_games_by_ids},
+        operations_match_job::{insert_match_jobs, get_match_jobs, set_match_job_status},
     }, 
     models::{
         team::Team, 
         errors::{MatchMakerError, self}, 
-        bot::Bot, 
+        bot::{Bot, CompileDiagnostic}, 
         game_2v2::{NewGame2v2, Game2v2, self}, 
-        competition::Competition, game_player_stats::{GamePlayerStats, GameError}
-    }, controllers::elo::update_team_elo
+        game_player_stats::{GamePlayerStats, GameError},
+        match_job::{MatchJob, NewMatchJob},
+        competition::{Competition, SandboxLimits},
+        round_plan::{RoundPlan, PlannedMatch, SkippedTeam}
+    }, controllers::{elo::update_team_elo, leaderboard::snapshot_leaderboard, pairing::create_match_pairs}
 };
 
-use super::{command_executor::{execute_command, recursive_copy}, elo::calc_elo_changes, file_handler::save_to_zip};
+use super::{game_log::{GameOutcome, PLAYER_KEYS, game_outcome, parse_events}, java_sources::{PLAYER_CLASS, flatten_single_folder, parse_javac_diagnostics}, language::{read_manifest, select_runtime, write_launcher}, worker::remote_workers_enabled, sandbox::{Sandbox, sandbox_limits}, command_executor::recursive_copy, elo::calc_elo_changes, file_handler::{extract_zip, save_to_zip, sha256_file}};
+
+/// Held while a round is finalized, so results arriving at the same time finalize it only once.
+static FINALIZE_LOCK: Mutex<()> = Mutex::new(());
+
+/// The evaluator playing the games.
+const EVALUATOR_JAR: &str = "resources/gamefiles/Evaluator.jar";
+
+/// The thread pool all games are played in, shared by the ladder rounds, final evaluations and
+/// brackets so they don't oversubscribe the CPUs when they run at the same time.
+static MATCH_POOL: Lazy<rayon::ThreadPool> = Lazy::new(match_thread_pool);
 
 /// Runs a 2v2 round for a specified competition.
 ///
 /// This function manages the execution of a single 2v2 round for a competition, which includes:
 /// 1. Fetching the competition details from the database.
-/// 2. Retrieving all the teams participating in the competition.
-/// 3. Compiling the bots for each team.
-/// 4. Creating match pairs for the round.
-/// 5. Running each match in parallel.
-/// 6. Cleaning up the match directory after all games have been executed.
-/// 7. Incrementing the competition round for the next set of matches.
+/// 2. Planning the round: retrieving the teams, compiling their bots and creating match pairs with
+///    the competition's pairing strategy. Every planned match is stored as a `pending` match job.
+///    If jobs were already stored for the current round (e.g. the process died in the middle of
+///    the round), they are resumed instead of planning the round again.
+/// 3. Running every job that is not settled yet in parallel. If remote workers are enabled, the
+///    jobs are left to the workers instead, and the round is finalized once their results are in.
+/// 4. Finalizing the round once every job is settled, see `finalize_2v2_round`.
 ///
 /// # Arguments
 ///
@@ -38,18 +55,15 @@ use super::{command_executor::{execute_command, recursive_copy}, elo::calc_elo_c
 ///
 /// # Returns
 ///
-/// A `Result` containing a `Vec` of tuples, where each tuple contains two teams that played against each other in the round. 
-/// If successful, or a `MatchMakerError` if there's an error.
+/// A `Result` which is `Ok(())` if the round was played, or a `MatchMakerError` if there's an error.
 ///
 /// # Errors
 ///
 /// This function will return an error if:
 /// - The competition cannot be fetched from the database.
 /// - The teams for the specified competition cannot be retrieved.
-/// - There's an issue compiling the bots for any team.
-/// - There's an error running any of the matches.
-/// - The cleanup process fails.
-/// - There's a problem updating the competition's round in the database.
+/// - The match jobs cannot be stored or fetched.
+/// - The round cannot be finalized.
 ///
 pub fn run_2v2_round(competition_id: String) -> Result<(), MatchMakerError> {
     println!("Running 2v2 competition: {}", competition_id);
@@ -58,35 +72,220 @@ pub fn run_2v2_round(competition_id: String) -> Result<(), MatchMakerError> {
         Err(e) => return Err(MatchMakerError::DatabaseError(e))
     };
 
+    let jobs = match get_match_jobs(competition.id.clone(), competition.round) {
+        Ok(jobs) => jobs,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+
+    let unsettled_jobs: Vec<MatchJob> = if jobs.is_empty() {
+        plan_2v2_round(&competition)?
+    } else {
+        println!("Resuming round {} of competition {}", competition.round, competition.id);
+        jobs.into_iter().filter(|job| !job.is_settled()).collect()
+    };
+
+    if !unsettled_jobs.is_empty() && remote_workers_enabled() {
+        println!("Left {} match jobs to the remote workers", unsettled_jobs.len());
+        return Ok(());
+    }
+
+    if !unsettled_jobs.is_empty() {
+        run_match_jobs(&competition, unsettled_jobs);
+    }
+
+    finalize_2v2_round(&competition)
+}
+
+/// Plans a round: creates the match pairs and stores them as pending match jobs.
+fn plan_2v2_round(competition: &Competition) -> Result<Vec<MatchJob>, MatchMakerError> {
     let teams = match get_teams_by_competition_id(competition.id.clone()) {
         Ok(teams) => teams,
         Err(e) => return Err(MatchMakerError::DatabaseError(e))
     };
 
-    let compiled_teams = compile_team_bots(teams);
-    let match_pairs = create_match_pairs(competition.games_per_round, compiled_teams);
+    let compiled_teams = compile_team_bots(teams, &competition.language);
+    let jobs = create_match_pairs(competition, compiled_teams)
+        .into_iter()
+        .map(|(team1, team2)| NewMatchJob::from(NewGame2v2::from_teams(
+            competition.id.clone(),
+            competition.round,
+            &team1,
+            &team2,
+        )))
+        .collect();
 
-    
-    // Get the number of available logical cores
-    let num_cores = num_cpus::get();
+    match insert_match_jobs(jobs) {
+        Ok(jobs) => Ok(jobs),
+        Err(e) => Err(MatchMakerError::DatabaseError(e))
+    }
+}
 
-    // Calculate the number of threads to use (one less than the number of cores)
-    let num_threads = num_cores - 1;
+/// Previews the next round of a competition without playing or storing anything.
+///
+/// The bots are compiled to find the teams that would be skipped, see `try_compile_bot`. The
+/// pairings are seeded with the competition and its round (see `create_match_pairs`), so they
+/// are the ones the round is started with, as long as the teams don't change in between. If
+/// the current round was already planned, the stored matches are returned instead, since
+/// running the round would resume them.
+///
+/// # Arguments
+///
+/// * `competition` - The competition whose round is previewed.
+///
+/// # Returns
+///
+/// A `Result` containing the planned matches and skipped teams, or a `MatchMakerError` if the
+/// teams or the stored matches cannot be fetched.
+///
+pub fn preview_2v2_round(competition: &Competition) -> Result<RoundPlan, MatchMakerError> {
+    let jobs = match get_match_jobs(competition.id.clone(), competition.round) {
+        Ok(jobs) => jobs,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+    if !jobs.is_empty() {
+        return Ok(RoundPlan {
+            competition_id: competition.id.clone(),
+            round: competition.round,
+            resuming: true,
+            matches: jobs.into_iter().map(PlannedMatch::from).collect(),
+            skipped_teams: Vec::new(),
+        });
+    }
 
-    // Create a custom thread pool with a specified number of threads
-    let pool = rayon::ThreadPoolBuilder::new()
-        .num_threads(num_threads)
-        .build()
-        .unwrap();
+    let teams = match get_teams_by_competition_id(competition.id.clone()) {
+        Ok(teams) => teams,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+    let (compiled_teams, skipped_teams) = check_teams(teams, |bot| try_compile_bot(bot, &competition.language));
+    let matches = create_match_pairs(competition, compiled_teams)
+        .iter()
+        .map(|(team1, team2)| PlannedMatch::from_teams(team1, team2))
+        .collect();
 
+    Ok(RoundPlan {
+        competition_id: competition.id.clone(),
+        round: competition.round,
+        resuming: false,
+        matches,
+        skipped_teams,
+    })
+}
+
+/// Finalizes the current round of a competition once all its match jobs are settled.
+///
+/// The ELO of the teams is updated with the games of the `done` jobs, a snapshot of the
+/// standings is stored and the competition moves to the next round. If some jobs are still
+/// pending or running, or the round was already finalized, nothing happens.
+///
+/// # Errors
+///
+/// Returns a `MatchMakerError` if the jobs or games cannot be fetched, the ELO changes or the
+/// leaderboard snapshot cannot be stored, or the round cannot be incremented.
+///
+pub fn finalize_2v2_round(competition: &Competition) -> Result<(), MatchMakerError> {
+    let _guard = FINALIZE_LOCK.lock().expect("Finalize mutex is poisoned");
+    let competition = match get_competition_by_id(competition.id.clone()) {
+        Ok(c) if c.round == competition.round => c,
+        Ok(_) => return Ok(()),
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+
+    let jobs = match get_match_jobs(competition.id.clone(), competition.round) {
+        Ok(jobs) => jobs,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+    if !jobs.iter().all(|job| job.is_settled()) {
+        println!("Round {} of competition {} has unsettled jobs, not finalizing yet", competition.round, competition.id);
+        return Ok(());
+    }
+
+    let game_ids = jobs
+        .into_iter()
+        .filter(|job| job.status.eq("done"))
+        .map(|job| job.id)
+        .collect();
+    let games_vec = match get_games_by_ids(game_ids) {
+        Ok(games) => games,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+
+    if let Err(e) = update_team_elo(games_vec) {
+        return Err(MatchMakerError::DatabaseError(e.into()))
+    }; 
+
+    // record the standings at the end of the round
+    if let Err(e) = snapshot_leaderboard(&competition) {
+        return Err(MatchMakerError::DatabaseError(e))
+    }
+
+    // increment competition round
+    let new_round = competition.round + 1;
+    if let Err(e) = set_competition_round(competition.id.clone(), new_round) {
+        return Err(MatchMakerError::DatabaseError(e))
+    }  
+    println!("Competition done!");
+    Ok(())
+}
+
+/// Runs the given match jobs in parallel and settles each of them.
+///
+/// A job is marked `running` while its game is played, then `done` once the game is stored or
+/// `failed` if the game could not be played. A job whose game was already stored (e.g. the
+/// process died right after storing it) is marked `done` without playing it again.
+///
+/// # Arguments
+///
+/// * `competition` - The competition the jobs are played in.
+/// * `jobs` - The match jobs to be run.
+///
+pub fn run_match_jobs(competition: &Competition, jobs: Vec<MatchJob>) {
+    MATCH_POOL.install(|| {
+        jobs.into_par_iter().for_each(|job| {
+            if let Err(e) = run_match_job(competition, &job) {
+                eprintln!("Error on storing state of match job {}: {:?}", job.id, e);
+            }
+        });
+    });
+}
+
+fn run_match_job(competition: &Competition, job: &MatchJob) -> Result<(), diesel::result::Error> {
+    if get_game_by_id(job.id.clone()).is_ok() {
+        return set_match_job_status(job.id.clone(), "done".to_string(), "".to_string());
+    }
+
+    set_match_job_status(job.id.clone(), "running".to_string(), "".to_string())?;
+    match run_match(competition, job.to_game()) {
+        Ok(_) => set_match_job_status(job.id.clone(), "done".to_string(), "".to_string()),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            set_match_job_status(job.id.clone(), "failed".to_string(), e.to_string())
+        }
+    }
+}
+
+/// Runs the given games in parallel.
+///
+/// The games are played in the shared match thread pool, with one thread less than the number
+/// of available logical cores. Games that fail to run are logged and left out of the result.
+///
+/// # Arguments
+///
+/// * `competition` - The competition the games are played in.
+/// * `match_games` - The games to be played.
+///
+/// # Returns
+///
+/// The games that were successfully played and stored.
+///
+pub fn run_matches(competition: &Competition, match_games: Vec<NewGame2v2>) -> Vec<Game2v2> {
     // Create a thread-safe vector using Arc and Mutex
     let games: Arc<Mutex<Vec<Game2v2>>> = Arc::new(Mutex::new(Vec::new()));
 
 
-    // Execute the parallel operation with the custom thread pool
-    pool.install(|| {
-        match_pairs.par_iter().for_each(|match_pair| {
-            match run_match(&competition, &match_pair.0, &match_pair.1) {
+    // Execute the parallel operation with the shared thread pool
+    MATCH_POOL.install(|| {
+        match_games.into_par_iter().for_each(|match_game| {
+            match run_match(competition, match_game) {
                 Ok(g) => {
                     let mut games_lock = games.lock().unwrap();
                     games_lock.push(g)
@@ -101,23 +300,24 @@ pub fn run_2v2_round(competition_id: String) -> Result<(), MatchMakerError> {
         .expect("Arc::try_unwrap failed, there are multiple owners of the Arc");
 
     // Lock the Mutex to access the vector
-    let games_vec = games_mutex.into_inner()
-        .expect("Mutex::into_inner failed, the mutex is poisoned");
+    games_mutex.into_inner()
+        .expect("Mutex::into_inner failed, the mutex is poisoned")
+}
 
-    if let Err(e) = update_team_elo(games_vec) {
-        return Err(MatchMakerError::DatabaseError(e.into()))
-    }; 
-    
-    // Cleanup: Remove the match directory
-    cleanup_matches()?;
-    
-    // increment competition round
-    let new_round = competition.round + 1;
-    if let Err(e) = set_competition_round(competition.id.clone(), new_round) {
-        return Err(MatchMakerError::DatabaseError(e))
-    }  
-    println!("Competition done!");
-    Ok(())
+/// Creates the thread pool games are played in, with one thread less than the number of
+/// available logical cores.
+fn match_thread_pool() -> rayon::ThreadPool {
+    // Get the number of available logical cores
+    let num_cores = num_cpus::get();
+
+    // Calculate the number of threads to use (one less than the number of cores)
+    let num_threads = num_cores - 1;
+
+    // Create a custom thread pool with a specified number of threads
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .build()
+        .unwrap()
 }
 
 /// Cleans up the matches directory by removing all sub-directories.
@@ -127,12 +327,16 @@ pub fn run_2v2_round(competition_id: String) -> Result<(), MatchMakerError> {
 /// It ensures the top-level `matches` directory remains intact while all its
 /// sub-directories (representing individual matches) are deleted.
 ///
+/// Every match removes its own directory once it is over, so this only removes the leftovers
+/// of matches that were interrupted. It must only be called while no match is played, e.g.
+/// on startup.
+///
 /// # Returns
 ///
 /// A `Result` which is `Ok(())` if the cleanup was successful, or a `MatchMakerError` 
 /// if there's an error during the cleanup process.
 ///
-fn cleanup_matches() -> Result<(), MatchMakerError> {
+pub fn cleanup_matches() -> Result<(), MatchMakerError> {
     // Cleanup: Remove all sub-directories within the ./resources/matches/ directory
     let matches_path = Path::new("./resources/matches");
     if let Ok(entries) = fs::read_dir(matches_path) {
@@ -147,72 +351,20 @@ fn cleanup_matches() -> Result<(), MatchMakerError> {
         }
     }
 
-    if let Err(e) = kill_java_player_processes() {
-        eprintln!("Failed killing java processes: {:?}", e);
-    }
-    Ok(())
-}
-
-
-/// Kill all processes running with the command "java Player."
-fn kill_java_player_processes() -> Result<(), std::io::Error> {
-    // Get a list of all processes with "java Player" in their command line
-    let ps_output = Command::new("ps")
-        .arg("ax")
-        .output()?;
-
-    // Convert the output to a string
-    let ps_output_str = String::from_utf8_lossy(&ps_output.stdout);
-
-    // Split the output into lines
-    let process_lines: Vec<&str> = ps_output_str.lines().collect();
-
-    // Iterate through the lines and find processes with "java Player"
-    for process_line in process_lines {
-        if process_line.contains("java Player") {
-            // Extract the process ID (PID)
-            let pid_str = process_line.split_whitespace().next().unwrap_or_default();
-
-            // Parse the PID as an integer
-            if let Ok(pid) = pid_str.parse::<i32>() {
-                // Kill the process using the "kill" command
-                let kill_result = Command::new("kill")
-                    .arg("-9") // Use SIGKILL to forcefully terminate the process
-                    .arg(pid.to_string())
-                    .output();
-
-                match kill_result {
-                    Ok(Output {
-                        status,
-                        stdout,
-                        stderr,
-                    }) => {
-                        if status.success() {
-                            println!("Killed process with PID {}: {:?}", pid, String::from_utf8_lossy(&stdout));
-                        } else {
-                            eprintln!("Failed to kill process with PID {}: {:?}", pid, String::from_utf8_lossy(&stderr));
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Error killing process with PID {}: {:?}", pid, e);
-                    }
-                }
-            }
-        }
-    }
-
     Ok(())
 }
 
 
-/// Runs a game match between two teams in a given competition.
+/// Runs a prepared game match between two teams.
 ///
 /// This function manages the preparation, execution, and cleanup of a game match between two teams.
 /// The steps include:
 ///
-/// 1. Initializing a new 2v2 game instance based on the teams and competition details.
-/// 2. Creating a unique directory for the match within the `./resources/matches` folder.
-/// 3. Copying the bots of both teams to the match directory.
+/// 1. Determining the replay directory of the game (per round for ladder games, per evaluation
+///    for final evaluation games).
+/// 2. Creating a directory for the match within the `./resources/matches` folder, named after
+///    the game, so concurrent rounds and evaluations never share one.
+/// 3. Copying the compiled bots of both teams to the match directory, see `compile_bot`.
 /// 4. Running the game using the Evaluator JAR, ensuring the game and its spawned bot processes 
 ///    are grouped together for easy management.
 /// 5. Saving the game's output to a file within the `./resources/games` folder.
@@ -222,9 +374,7 @@ fn kill_java_player_processes() -> Result<(), std::io::Error> {
 ///
 /// # Arguments
 ///
-/// * `competition` - A reference to the competition in which the teams are participating.
-/// * `team1` - The first team participating in the match.
-/// * `team2` - The second team participating in the match.
+/// * `match_game` - The game to be played, holding the competition, teams and bots of the match.
 ///
 /// # Returns
 ///
@@ -236,43 +386,55 @@ fn kill_java_player_processes() -> Result<(), std::io::Error> {
 /// This function may return one of the following errors:
 ///
 /// - `MatchMakerError::IOError` if there is an I/O error during file operations.
-/// - `MatchMakerError::TimeoutError` if the game process exceeds the specified timeout.
-/// - `MatchMakerError::GameProcessFailed` if the game process exits with an error.
+/// - `MatchMakerError::DatabaseError` if a bot of the game cannot be fetched.
+/// - `MatchMakerError::SandboxError` if the game cannot be played in a sandbox.
+///
+/// A game exceeding the competition's match timeout, or whose evaluator fails, is not an
+/// error: it is stored with the outcome `timeout` or `aborted` and rated according to the
+/// competition's timeout policy.
 ///
 /// # Notes
 /// 
 /// - This function assumes that the necessary external tools and JAR files for game evaluation are
 ///   available and correctly configured.
 /// 
-fn run_match(competition: &Competition, team1: &Team, team2: &Team) -> Result<Game2v2, MatchMakerError> {
-    // Initialize a new 2v2 game with details from the provided teams and competition
-    let mut match_game = NewGame2v2::new(
-        competition.id.clone(),
-        competition.round,
-        team1.id.clone(),
-        team2.id.clone(),
-        team1.bot1.clone(),
-        team1.bot2.clone(),
-        team2.bot1.clone(),
-        team2.bot2.clone(),
-    );
-
-    // Create a directory to store match-related files
+fn run_match(competition: &Competition, match_game: NewGame2v2) -> Result<Game2v2, MatchMakerError> {
+    // Create a directory to store match-related files, without leftovers of an interrupted run
     let match_folder = Path::new("./resources/matches").join(match_game.id.to_string());
+    if match_folder.exists() {
+        fs::remove_dir_all(&match_folder).map_err(MatchMakerError::IOError)?;
+    }
     if let Err(e) = fs::create_dir_all(&match_folder) {
         return Err(MatchMakerError::IOError(e));
     }
 
+    let result = play_match(competition, &match_folder, match_game);
+    if let Err(e) = fs::remove_dir_all(&match_folder) {
+        eprintln!("Error on removing match directory {:?}: {}", match_folder, e);
+    }
+    result
+}
+
+fn play_match(competition: &Competition, match_folder: &Path, mut match_game: NewGame2v2) -> Result<Game2v2, MatchMakerError> {
     // create a round directory (if doesn't exist) to later store game replays
-    let output_dir = format!("./resources/games/{}", competition.round);
+    let output_dir = replay_directory(&match_game);
     if let Err(e) = fs::create_dir_all(&output_dir) {
         return Err(MatchMakerError::IOError(e));
     }
 
-    // Copy each bot from the work directory to the match directory
-    let bots = vec![&team1.bot1, &team1.bot2, &team2.bot1, &team2.bot2];
+    // Copy each compiled bot to the match directory
+    let bots = [
+        match_game.team1bot1_id.clone(),
+        match_game.team1bot2_id.clone(),
+        match_game.team2bot1_id.clone(),
+        match_game.team2bot2_id.clone(),
+    ];
     for bot_id in &bots {
-        let source = Path::new("./resources/workdir/bots").join(bot_id);
+        let bot = match get_bot_by_id(bot_id.clone()) {
+            Ok(b) => b,
+            Err(e) => return Err(MatchMakerError::DatabaseError(e)),
+        };
+        let source = compile_bot(&bot, &competition.language)?;
         let destination = match_folder.join(bot_id);
         
         if let Err(e) = recursive_copy(&source, &destination) {
@@ -280,7 +442,56 @@ fn run_match(competition: &Competition, team1: &Team, team2: &Team) -> Result<Ga
         }
     }
 
+    let timeout = Duration::from_secs(competition.match_timeout as u64);
+    let (output, errors) = play_game(match_folder, &mut match_game, &sandbox_limits(competition), timeout)?;
+    let output_file = format!("{}/{}.zip", output_dir, match_game.id.to_string());
+
+    // Save the game's output to the specified file
+    let output_string = output.join("\n");
+    if let Err(e) = save_to_zip(output_string, &output_file) {
+        return Err(e);
+    } else {
+        match_game.log_file_path = output_file;
+    }
+
+    // Save any errors to a separate file
+    save_game_errors(&match_game, &errors)?;
+
+    // Parse the game using the provided function and return the result
+    parse_game(output, errors, match_game)
+}
+
+/// Plays a game with the Evaluator JAR.
+///
+/// The bots of the game have to be in the match folder already, each in a directory named
+/// after its ID. The game is played in a `Sandbox` with the given limits, its processes may
+/// only write to the match folder and only see it and the evaluator (by their full path).
+///
+/// # Arguments
+///
+/// * `match_folder` - The folder holding the bots of the game.
+/// * `match_game` - The game to be played. Its outcome and the number of bot processes that
+///   were still running when it ended are stored in it (see `Sandbox::terminate`).
+/// * `limits` - The resource limits of the game's processes.
+/// * `timeout` - The time the game may take, it is killed afterwards and its outcome is `timeout`.
+///
+/// # Returns
+///
+/// A `Result` containing the lines written by the game to stdout and to stderr, or a
+/// `MatchMakerError` if the sandbox could not be set up (see `Sandbox::new`) or the game
+/// process could not be run.
+///
+pub fn play_game(match_folder: &Path, match_game: &mut NewGame2v2, limits: &SandboxLimits, timeout: Duration) -> Result<(Vec<String>, Vec<String>), MatchMakerError> {
+    let match_folder = fs::canonicalize(match_folder).map_err(MatchMakerError::IOError)?;
+    let evaluator = fs::canonicalize(EVALUATOR_JAR).map_err(MatchMakerError::IOError)?;
+
     // Execute the game using the Evaluator JAR and collect the paths of each bot
+    let bots = [
+        match_game.team1bot1_id.clone(),
+        match_game.team1bot2_id.clone(),
+        match_game.team2bot1_id.clone(),
+        match_game.team2bot2_id.clone(),
+    ];
     let mut bot_paths: Vec<String> = bots
         .iter()
         .map(|bot_id| match_folder
@@ -288,18 +499,17 @@ fn run_match(competition: &Competition, team1: &Team, team2: &Team) -> Result<Ga
             .to_string_lossy()
             .to_string())
         .collect();
-    let output_file = format!("./resources/games/{}/{}.zip", competition.round, match_game.id.to_string());
     let mut command_args = vec![
         "-jar".to_string(),
-        "resources/gamefiles/Evaluator.jar".to_string(),
+        evaluator.to_string_lossy().to_string(),
         "--gui=false".to_string(),
     ];
     command_args.append(&mut bot_paths);
 
     
-    // Spawn the child process
-    let mut child = Command::new("java")
-        .args(&command_args)
+    // Spawn the child process, the sandbox kills whatever it left behind once dropped
+    let sandbox = Sandbox::new(&match_folder, &[&evaluator], &match_game.id, limits.clone())?;
+    let mut child = sandbox.command("java", &command_args)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
@@ -328,56 +538,59 @@ fn run_match(competition: &Competition, team1: &Team, team2: &Team) -> Result<Ga
     });
 
     // Wait for the process to finish or timeout
-    let timeout_result: Option<ExitStatus> = child.wait_timeout(Duration::from_secs(120)).map_err(|e| MatchMakerError::IOError(e))?;
-    // Initialize flags for success and timeout
-    // let mut timeout_occurred = false;
-    // let mut success = true;
-    // Check if the process has finished
-    if let None = timeout_result {
-        // Timeout occurred
-        // timeout_occurred = true;
-        // success = false;
-        // Attempt to kill the child process
-        let _ = child.kill();
-        let st = child.wait();
-        println!("Game timed out, killed and exited with status: {:#?}", st);
+    let timeout_result: Option<ExitStatus> = child.wait_timeout(timeout).map_err(|e| MatchMakerError::IOError(e))?;
+    match timeout_result {
+        None => {
+            // Kill the whole game, not only the evaluator
+            let leftover = sandbox.terminate(child.id());
+            let st = child.wait();
+            println!("Game timed out, killed {} processes and exited with status: {:#?}", leftover, st);
+            match_game.leftover_processes = leftover as i32;
+            match_game.outcome = "timeout".to_string();
+        },
+        Some(status) => {
+            // the PID namespace usually ended the game's processes along with the evaluator,
+            // whatever is still left in the sandbox is counted and killed
+            let leftover = sandbox.terminate(child.id());
+            match_game.leftover_processes = leftover as i32;
+            match_game.outcome = if status.success() { "finished" } else { "aborted" }.to_string();
+        },
     }
 
     // Join the threads and collect the output
     let output: Vec<String> = stdout_handle.join().expect("Failed to join stdout thread");
     let errors: Vec<String> = stderr_handle.join().expect("Failed to join stderr thread");
 
-    // if timeout_occurred {
-    //     // Process did not finish in time
-    //     return Err(MatchMakerError::TimeoutError);
-    // } else if !success {
-    //     // Process finished but was not successful
-    //     return Err(MatchMakerError::GameProcessFailed);
-    // }
-
+    Ok((output, errors))
+}
 
-    // Save the game's output to the specified file
-    let output_string = output.join("\n");
-    if let Err(e) = save_to_zip(output_string, &output_file) {
-        return Err(e);
-    } else {
-        match_game.log_file_path = output_file;
+/// Saves the error output of a game next to its replay, if there is any.
+pub fn save_game_errors(match_game: &NewGame2v2, errors: &[String]) -> Result<(), MatchMakerError> {
+    if errors.concat().trim().eq("...") {
+        return Ok(());
     }
-
-    // Save any errors to a separate file
-    if !errors.concat().trim().eq("...") {
-        let error_string = errors.join("\n");
-        let error_file = format!("./resources/games/{}/{}_error.txt", competition.round, match_game.id.to_string());
-        if let Err(e) = fs::write(&error_file, &error_string) {
-            // Log error output to help diagnose problems
-            log::error!("Error output from child process: {}", error_string);
-            return Err(MatchMakerError::IOError(e));
-        }
+    let error_string = errors.join("\n");
+    let error_file = format!("{}/{}_error.txt", replay_directory(match_game), match_game.id);
+    if let Err(e) = fs::write(&error_file, &error_string) {
+        // Log error output to help diagnose problems
+        log::error!("Error output from child process: {}", error_string);
+        return Err(MatchMakerError::IOError(e));
     }
+    Ok(())
+}
 
-
-    // Parse the game using the provided function and return the result
-    parse_game(output, errors, match_game)
+/// Returns the directory in which the replay of a game is stored.
+///
+/// Ladder games are grouped by round, while final evaluation and bracket games are grouped by
+/// their evaluation or bracket.
+pub fn replay_directory(match_game: &NewGame2v2) -> String {
+    if !match_game.bracket_id.is_empty() {
+        format!("./resources/games/bracket/{}", match_game.bracket_id)
+    } else if !match_game.evaluation_id.is_empty() {
+        format!("./resources/games/final/{}", match_game.evaluation_id)
+    } else {
+        format!("./resources/games/{}", match_game.round)
+    }
 }
 
 /// Parses game output to determine match results and constructs a `Game2v2` object.
@@ -401,479 +614,361 @@ fn run_match(competition: &Competition, team1: &Team, team2: &Team) -> Result<Ga
 /// A `Result` containing a `Game2v2` object if successful, or a `MatchMakerError` if there's an error.
 ///
 fn parse_game(lines: Vec<String>, errors: Vec<String>, mut match_game: NewGame2v2) -> Result<Game2v2, MatchMakerError> {
-    if errors.len() > 1 { // always at least 1 because of first "..." row
-        parse_bugged_game(lines, errors, &mut match_game);
-    } else {
-        parse_healthy_game(lines, errors, &mut match_game);
-    }
-    
+    parse_game_result(lines, errors, &mut match_game);
+    store_game(match_game)
+}
 
-    if let Err(e) = calc_elo_changes(&mut match_game) {
-        return Err(MatchMakerError::DatabaseError(e.into()))
-    }
-    
-    match insert_game(match_game) {
-        Ok(g) => Ok(g),
-        Err(e) => Err(MatchMakerError::DatabaseError(e)),
+/// Stores the winner, the surviving bots and the statistics parsed from the game output in the game.
+///
+/// Lines of the output that cannot be read (see `parse_events`) are logged and left out.
+pub fn parse_game_result(lines: Vec<String>, errors: Vec<String>, match_game: &mut NewGame2v2) {
+    let (events, skipped) = parse_events(&lines, &errors);
+    for line in skipped {
+        eprintln!("Warning: skipped a line of the output of game {}: {}", match_game.id, line);
     }
-}
 
-fn parse_bugged_game(_lines: Vec<String>, errors: Vec<String>, match_game: &mut NewGame2v2) -> () {
-    // find bot id
     let bot_ids = [
         match_game.team1bot1_id.clone(),
         match_game.team1bot2_id.clone(),
         match_game.team2bot1_id.clone(),
         match_game.team2bot2_id.clone(),
     ];
-    let mut bugged_bot_id_option = None;
-    for row in errors.iter() {
-        for bot_id in bot_ids.iter() {
-            if row.contains(bot_id) {
-                bugged_bot_id_option = Some(bot_id);
-                break;
+    match game_outcome(&events, &bot_ids) {
+        GameOutcome::Played { survived, winner, stats } => {
+            set_survivors(match_game, survived);
+            set_winner(match_game, winner);
+
+            let stats: HashMap<&str, GamePlayerStats> = PLAYER_KEYS
+                .into_iter()
+                .zip(*stats)
+                .filter_map(|(key, stats)| stats.map(|stats| (key, stats)))
+                .collect();
+            match_game.additional_data = serde_json::to_string(&stats).unwrap_or(String::from("{ \"error\": \"Error serializing\"}"));
+        },
+        GameOutcome::Bugged { blame, error } => {
+            // the bot to blame is the only one that did not survive, its team loses
+            // without a bot to blame, the game is a draw
+            let mut survived = [true; 4];
+            if let Some(player) = blame {
+                survived[player] = false;
             }
-        }
+            set_survivors(match_game, survived);
+            set_winner(match_game, blame.map(|player| if player < 2 { 2 } else { 1 }));
+
+            let additional_data_error = GameError {
+                error: error.replace("\\", "\\\\"),
+                blame_id: blame.map(|player| bot_ids[player].clone()).unwrap_or("Unknown".to_string()),
+            };
+            match_game.additional_data = serde_json::to_string(&additional_data_error).unwrap_or(String::from("{ \"error\": \"Error serializing\"}"));
+        },
     }
-    match_game.team1bot1_survived = true;
-    match_game.team1bot2_survived = true;
-    match_game.team2bot1_survived = true;
-    match_game.team2bot2_survived = true;
-
-    if let Some(bugged_bot_id) = bugged_bot_id_option {
-        if &match_game.team1bot1_id == bugged_bot_id {
-            match_game.team1bot1_survived = false;
-            match_game.winner_id = match_game.team2_id.clone();
-        }
-
-        if &match_game.team1bot2_id == bugged_bot_id {
-            match_game.team1bot2_survived = false;
-            match_game.winner_id = match_game.team2_id.clone();
-        }
-
-        if &match_game.team2bot1_id == bugged_bot_id {
-            match_game.team2bot1_survived = false;
-            match_game.winner_id = match_game.team1_id.clone();
-        }
-
-        if &match_game.team2bot2_id == bugged_bot_id {
-            match_game.team2bot2_survived = false;
-            match_game.winner_id = match_game.team1_id.clone();
-        }
-    }
-
-    let trimmed_lines: String = errors
-        .join("\n")
-        .replace("\\", "\\\\");
+}
 
-    // Remove backslashes from the formatted string
-    let additional_data_error = GameError {
-        error: trimmed_lines,
-        blame_id: bugged_bot_id_option.unwrap_or(&"Unknown".to_string()).to_string()
+/// Stores the team that won (1 or 2) in the game, or a draw if there is none.
+fn set_winner(match_game: &mut NewGame2v2, winner: Option<u8>) {
+    match_game.draw = winner.is_none();
+    match_game.winner_id = match winner {
+        Some(1) => match_game.team1_id.clone(),
+        Some(_) => match_game.team2_id.clone(),
+        None => "".to_string(),
     };
-
-    match_game.additional_data = serde_json::to_string(&additional_data_error).unwrap_or(String::from("{ \"error\": \"Error serializing\"}"));
 }
 
-fn parse_healthy_game(lines: Vec<String>, _errors: Vec<String>, match_game: &mut NewGame2v2) -> () {
-    let mut r_green = 0;
-    let mut r_blue = 0;
-    let mut r_yellow = 0;
-    let mut r_cyan = 0;
-    let mut current_bot: Option<String> = None;
-    let mut last_L: Option<String> = None;
-    let mut stats: HashMap<String, GamePlayerStats> = HashMap::new();
-    let mut stats_keys = vec![
-        "team2bot2",
-        "team1bot2",
-        "team2bot1",
-        "team1bot1", 
-    ];
-
-
-
-    for line in lines.into_iter() {
-        // track score through the game
-        // the last score is the final score of the game
-        // needed to determine the winner (if timeout still both teams are alive
-        // and the "survived"/"winnwe" stat at the end is not enough)
-        if line.contains("R ") {
-            let parts: Vec<&str> = line.split(" ").collect();
-            if parts.len() == 3 {
-                match parts[2] {
-                    "green"     => r_green = parts[1].parse().unwrap_or(0),
-                    "blue"      => r_blue = parts[1].parse().unwrap_or(0),
-                    "yellow"    => r_yellow = parts[1].parse().unwrap_or(0),
-                    "cyan"      => r_cyan = parts[1].parse().unwrap_or(0),
-                    _ => ()
-                }
-            }
-        }
-
-        if line.contains("L ") {
-            last_L = Some(line.to_owned());
-        }
-
-        if line.contains("STAT: ") {
-            // try to extract a bot name
-            // also init a stat object for the player (untill next player id there is going to 
-            // be a sequence of stats in form of <key>: <value> for this player)
-            let next_key_option = stats_keys.pop();
-            if let Some(next_key) = next_key_option {
-                current_bot = Some(next_key.to_string());
-                stats.insert(next_key.into(), GamePlayerStats::default());
-            }
-        }
-
-
-        let parts: Vec<&str> = line.split(" ").collect();
-        
-        // if collecting player stats
-        if let Some(bot_key) = &current_bot {
-            if parts.len() == 2 {
-                
-                let stat = match stats.get_mut(bot_key) {
-                    Some(s) => s,
-                    None => continue,
-                };
-                
-                match parts[0] {
-                    "turnsPlayed:"           => stat.turns_played             = parts[1].parse().unwrap_or(0),
-                    "survive:"               => stat.survived                 = parts[1].parse().unwrap_or(false),
-                    "fleetGenerated:"        => stat.fleet_generated          = parts[1].parse().unwrap_or(0),
-                    "fleetLost:"             => stat.fleet_lost               = parts[1].parse().unwrap_or(0),
-                    "fleetReinforced:"       => stat.fleet_reinforced         = parts[1].parse().unwrap_or(0),
-                    "largestAttack:"         => stat.largest_attack           = parts[1].parse().unwrap_or(0),
-                    "largestLoss:"           => stat.largest_loss             = parts[1].parse().unwrap_or(0),
-                    "largestReinforcement:"  => stat.largest_reinforcement    = parts[1].parse().unwrap_or(0),
-                    "planetsLost:"           => stat.planets_lost             = parts[1].parse().unwrap_or(0),
-                    "planetsConquered:"      => stat.planets_conquered        = parts[1].parse().unwrap_or(0),
-                    "planetsDefended:"       => stat.planets_defended         = parts[1].parse().unwrap_or(0),
-                    "planetsAttacked:"       => stat.planets_attacked         = parts[1].parse().unwrap_or(0),
-                    "numFleetLost:"          => stat.num_fleet_lost            = parts[1].parse().unwrap_or(0),
-                    "numFleetReinforced:"    => stat.num_fleet_reinforced      = parts[1].parse().unwrap_or(0),
-                    "numFleetGenerated:"     => stat.num_fleet_generated       = parts[1].parse().unwrap_or(0),
-                    "totalTroopsGenerated:"  => stat.total_troops_generated    = parts[1].parse().unwrap_or(0),
-                    _ => ()
-                }
-            }
-        }
-    }
+fn set_survivors(match_game: &mut NewGame2v2, survived: [bool; 4]) {
+    match_game.team1bot1_survived = survived[0];
+    match_game.team1bot2_survived = survived[1];
+    match_game.team2bot1_survived = survived[2];
+    match_game.team2bot2_survived = survived[3];
+}
 
-    // check if bots survived
-    match_game.team1bot1_survived = if let Some(stat) = stats.get("team1bot1") {
-        stat.survived
-    } else {
-        false
-    };
-    match_game.team1bot2_survived = if let Some(stat) = stats.get("team1bot2") {
-        stat.survived
-    } else {
-        false
-    };
-    match_game.team2bot1_survived = if let Some(stat) = stats.get("team2bot1") {
-        stat.survived
-    } else {
-        false
-    };
-    match_game.team2bot2_survived = if let Some(stat) = stats.get("team2bot2") {
-        stat.survived
-    } else {
-        false
+/// Calculates the ELO changes of a played game and stores the game.
+pub fn store_game(mut match_game: NewGame2v2) -> Result<Game2v2, MatchMakerError> {
+    let competition = match get_competition_by_id(match_game.competition_id.clone()) {
+        Ok(c) => c,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e)),
     };
-
-    match (
-        &match_game.team1bot1_survived,
-        &match_game.team1bot2_survived,
-        &match_game.team2bot1_survived,
-        &match_game.team2bot2_survived
-    ) {
-        (true,  true,  false, false) => match_game.winner_id = match_game.team1_id.clone(),
-        (true,  false, false, false) => match_game.winner_id = match_game.team1_id.clone(),
-        (false, true,  false, false) => match_game.winner_id = match_game.team1_id.clone(),
-        (false, false, true,  true)  => match_game.winner_id = match_game.team2_id.clone(),
-        (false, false, true,  false) => match_game.winner_id = match_game.team2_id.clone(),
-        (false, false, false, true)  => match_game.winner_id = match_game.team2_id.clone(),
-        (_, _, _, _) => match_game.winner_id = "".to_string(),
-    }
-
-    // if multiple teams alive at the end (timeout) check who won by score
-    if match_game.winner_id.eq("") {
-        let t1_score = r_yellow + r_green;
-        let t2_score = r_blue + r_cyan;
-        
-        if t1_score > t2_score {
-            match_game.winner_id = match_game.team1_id.clone();
-        } else {
-            match_game.winner_id = match_game.team2_id.clone();
-        }
+    if let Err(e) = calc_elo_changes(&mut match_game, &competition.timeout_policy) {
+        return Err(MatchMakerError::DatabaseError(e.into()))
     }
-    if stats.is_empty() && last_L.is_some() {
-        parse_bugged_game(vec![], vec![last_L.unwrap()], match_game)
-    } else {
-        match_game.additional_data = serde_json::to_string(&stats).unwrap_or(String::from("{ \"error\": \"Error serializing\"}"));
+    
+    match insert_game(match_game) {
+        Ok(g) => Ok(g),
+        Err(e) => Err(MatchMakerError::DatabaseError(e)),
     }
 }
 
+/// Attempts to compile the bots associated with each team in parallel.
+///
+/// The teams that were skipped are left out of the result. See `compile_teams` for details.
+///
+/// # Arguments
+///
+/// * `teams` - A vector of `Team` objects for which bots need to be compiled.
+/// * `language` - The language of the teams' competition, empty to detect it.
+///
+/// # Returns
+///
+/// * A vector of `Team` objects for which both bots were successfully compiled.
+///
+pub fn compile_team_bots(teams: Vec<Team>, language: &str) -> Vec<Team> {
+    compile_teams(teams, language).0
+}
 
 /// Attempts to compile the bots associated with each team in parallel.
 ///
 /// This function performs the following steps for each team:
 /// 1. If a team doesn't have both bot1 and bot2, the team is skipped.
 /// 2. Retrieves the details of bot1 and bot2. If there's an error fetching the details, the team is skipped.
-/// 3. Tries to compile bot1 and bot2. If there's a compilation error, an error is set for the respective bot.
+/// 3. Tries to compile bot1 and bot2 with `compile_bot`, which only compiles bots that were
+///    not compiled before. If there's a compilation error, the team is skipped.
 /// 4. Teams with successful bot compilations are collected and returned.
 ///
 /// # Arguments
 ///
 /// * `teams` - A vector of `Team` objects for which bots need to be compiled.
+/// * `language` - The language of the teams' competition, empty to detect it.
 ///
 /// # Returns
 ///
-/// * A vector of `Team` objects for which both bots were successfully compiled.
+/// * A vector of `Team` objects for which both bots were successfully compiled, and the
+///   skipped teams with the reason they were skipped.
 ///
 /// # Notes
 ///
 /// This function uses parallel processing for improved performance. Each team's bots are compiled in a separate thread.
 ///
-pub fn compile_team_bots(teams: Vec<Team>) -> Vec<Team> {
+pub fn compile_teams(teams: Vec<Team>, language: &str) -> (Vec<Team>, Vec<SkippedTeam>) {
+    check_teams(teams, |bot| compile_bot(bot, language).map(|_| ()))
+}
+
+/// Checks the bots of each team in parallel with `compile`, see `compile_teams`.
+fn check_teams(teams: Vec<Team>, compile: impl Fn(&Bot) -> Result<(), MatchMakerError> + Sync) -> (Vec<Team>, Vec<SkippedTeam>) {
     // Parallel processing of each team to compile associated bots
-    let results: Vec<Team> = teams.into_par_iter().filter_map(|team| {
+    let results: Vec<Result<Team, SkippedTeam>> = teams.into_par_iter().map(|team| {
         // Skip teams without both bot1 and bot2
         if team.bot1.eq("") || team.bot2.eq("") {
-            return None
-        }
-
-        // Retrieve bot details
-        let bot1 = match get_bot_by_id(team.bot1.clone()) {
-            Ok(b) => b,
-            Err(_) => return None,
-        };
-
-        let bot2 = match get_bot_by_id(team.bot2.clone()) {
-            Ok(b) => b,
-            // Err(e) => return Some(Err(MatchMakerError::DatabaseError(e))),
-            Err(_) => return None,
-        };
-        
-        // Attempt to compile bot1
-        if let Err(e) = compile_bot(&bot1) {
-            if let Err(_) = set_bot_error(bot1, e.to_string()) {
-                // return Some(Err(MatchMakerError::DatabaseError(e)));
-                return None;
-            }
-            // return Some(Err(e))
-            return None
+            return Err(SkippedTeam::new(&team, "The team has not selected two bots".to_string()))
         }
 
-        // Attempt to compile bot2
-        if let Err(e) = compile_bot(&bot2) {
-            if let Err(_) = set_bot_error(bot2, e.to_string()) {
-                // return Some(Err(MatchMakerError::DatabaseError(e)));
-                return None;
+        for bot_id in [&team.bot1, &team.bot2] {
+            // Retrieve bot details
+            let bot = match get_bot_by_id(bot_id.clone()) {
+                Ok(b) => b,
+                Err(_) => return Err(SkippedTeam::new(&team, format!("Bot {} does not exist", bot_id))),
+            };
+
+            // Attempt to compile the bot
+            if let Err(e) = compile(&bot) {
+                let reason = format!("Bot {} failed to compile: {}", bot.id, e.to_string().trim());
+                return Err(SkippedTeam::new(&team, reason))
             }
-            // return Some(Err(e))
-            return None
         }
 
         // Return the team if both bots compiled successfully
-        Some(team)
+        Ok(team)
     }).collect();
 
-    results
-    // // Extract teams with successful bot compilations
-    // let compiled_teams: Vec<Team> = results.into_iter().filter_map(|res| {
-    //     match res {
-    //         Ok(team) => Some(team),
-    //         Err(_) => None,
-    //     }
-    // }).collect();
-
-    // compiled_teams
-}
-
-
-/// Check if a file contains the string "public static void main("
-fn contains_main_method(file_path: &str) -> io::Result<bool> {
-    let file = File::open(file_path)?;
-
-    for line in io::BufReader::new(file).lines() {
-        let line = line?;
-        // Check if the line contains the desired string
-        if line.contains("public static void main(") {
-            return Ok(true);
+    let mut compiled_teams = Vec::new();
+    let mut skipped_teams = Vec::new();
+    for result in results.into_iter() {
+        match result {
+            Ok(team) => compiled_teams.push(team),
+            Err(skipped) => skipped_teams.push(skipped),
         }
     }
-
-    Ok(false)
+    (compiled_teams, skipped_teams)
 }
 
-/// Compiles the provided bot's source code.
+
+/// Compiles a bot, unless a bot with the same source was compiled before.
 ///
-/// This function performs the following tasks:
-/// 1. Creates a working directory specific to the bot.
-/// 2. Copies the bot's ZIP file to the working directory.
-/// 3. Unzips the bot's ZIP file.
-/// 4. Finds any Java files inside the unzipped directory.
-/// 5. Compiles the Java files using the `javac` command.
+/// Compiled bots are stored under `./resources/workdir/artifacts/{hash}`, keyed by the SHA-256
+/// hash of the uploaded ZIP file, so bots are compiled once (usually on upload) and rounds reuse
+/// the compiled files. If the bot's competition has a language set, the key is
+/// `{hash}-{language}` instead, as the same source may be compiled differently then. Whether a
+/// bot compiles is decided once per language as well: the result is stored on the bot along
+/// with the language, `compile_status` is `compiled` or `failed` afterwards. Only failures
+/// caused by the bot's source are stored, a compilation failing for other reasons (e.g. a full
+/// disk) is tried again the next time.
 ///
 /// # Arguments
 ///
-/// * `bot` - A `Bot` instance containing the bot's details, including the source path.
+/// * `bot` - The bot to be compiled.
+/// * `language` - The language of the bot's competition, empty to detect it.
 ///
 /// # Returns
 ///
-/// * `Ok(())` if the bot's source code was compiled successfully.
-/// * `Err(MatchMakerError)` if any step in the process fails.
+/// A `Result` containing the directory of the compiled bot.
 ///
 /// # Errors
 ///
-/// This function will return an error if:
-/// * The working directory cannot be created.
-/// * The ZIP file cannot be copied or unzipped.
-/// * No Java files are found in the unzipped directory.
-/// * The Java files cannot be compiled.
-/// 
-pub fn compile_bot(bot: &Bot) -> Result<(), MatchMakerError> {
-    let workdir = Path::new("./resources/workdir/bots").join(bot.id.clone());
-    let source_path = Path::new(&bot.source_path);
-
-    // Create a dedicated working directory for the bot.
-    if let Err(e) = fs::create_dir_all(&workdir) {
-        return Err(MatchMakerError::IOError(e));
+/// Returns a `MatchMakerError::CompileError` with the stored error if the bot failed to compile
+/// before, or the error of the compilation if it fails now.
+///
+pub fn compile_bot(bot: &Bot, language: &str) -> Result<PathBuf, MatchMakerError> {
+    if bot.compile_status.eq("failed") && bot.compile_language.eq(language) {
+        return Err(MatchMakerError::CompileError(bot.compile_error.trim().to_string()));
     }
 
-    // Convert the paths to string representations for command execution.
-    let workdir_str = match workdir.as_os_str().to_str() {
-        Some(s) => s,
-        None => return Err(MatchMakerError::InvalidPath(workdir.into())),
-    };
-    let source_path_str = match source_path.as_os_str().to_str() {
-        Some(s) => s,
-        None => return Err(MatchMakerError::InvalidPath(source_path.into())),
-    };
-
-    // Copy the bot's ZIP file to its working directory.
-    if let Err(e) = execute_command(
-        "cp".to_string(), 
-        vec![source_path_str, workdir_str]
-    ) {
-        return Err(MatchMakerError::IOError(e))
+    // bots uploaded before artifacts were cached have no hash yet
+    let hash = if bot.source_hash.is_empty() {
+        sha256_file(Path::new(&bot.source_path)).map_err(MatchMakerError::IOError)?
+    } else {
+        bot.source_hash.clone()
     };
+    let key = artifact_key(&hash, language);
+    let artifact = Path::new("./resources/workdir/artifacts").join(&key);
+    if artifact.is_dir() {
+        if !bot.compile_status.eq("compiled") || bot.source_hash.is_empty() || !bot.compile_language.eq(language) {
+            store_compile_result(bot, hash, language, "compiled", "".to_string(), Vec::new());
+        }
+        return Ok(artifact);
+    }
 
-    // Extract the file name from the source path.
-    let file_name_osstr = match source_path.file_name() {
-        Some(n) => n,
-        None => return Err(MatchMakerError::InvalidPath(source_path.into())),
-    };
-    
-    let file_name_str = match file_name_osstr.to_str() {
-        Some(s) => s,
-        None => return Err(MatchMakerError::InvalidPath(source_path.into())),
-    };
+    // bots with the same source may be compiled at the same time, so each is compiled in its
+    // own directory, which is then moved in place
+    let workdir = Path::new("./resources/workdir/artifacts").join(format!("{}.{}.tmp", key, bot.id));
+    let _ = fs::remove_dir_all(&workdir);
+    let result = compile_source(Path::new(&bot.source_path), &workdir, language);
+    if let Err(e) = result {
+        let _ = fs::remove_dir_all(&workdir);
+        if !is_source_error(&e) {
+            return Err(e);
+        }
+        // compilers report the files relative to the bot's directory, other errors may still
+        // name the working directory
+        let error = e.to_string().replace(&format!("{}/", workdir.display()), "");
+        let diagnostics = parse_javac_diagnostics(&e.to_string(), &workdir);
+        store_compile_result(bot, hash, language, "failed", error.clone(), diagnostics);
+        return Err(MatchMakerError::CompileError(error));
+    }
 
-    // Unzip the bot's ZIP file in the working directory.
-    let unzip_target = workdir.join(file_name_str);
-    let unzip_target_str = match unzip_target.as_os_str().to_str() {
-        Some(s) => s,
-        None => return Err(MatchMakerError::InvalidPath(unzip_target.into())),
-    };
-    
-    if let Err(e) = execute_command(
-        "unzip".to_string(), 
-        vec!["-o", unzip_target_str, "-d", workdir_str]
-    ) {
-        return Err(MatchMakerError::IOError(e));
+    if let Err(e) = fs::rename(&workdir, &artifact) {
+        let _ = fs::remove_dir_all(&workdir);
+        // the artifact exists if another bot with the same source was faster
+        if !artifact.is_dir() {
+            return Err(MatchMakerError::IOError(e));
+        }
     }
+    store_compile_result(bot, hash, language, "compiled", "".to_string(), Vec::new());
+    Ok(artifact)
+}
 
-    // Retrieve a list of Java files from the unzipped directory.
-    let java_files: Vec<String> = match fs::read_dir(&workdir) {
-        Ok(entries) => entries
-            .filter_map(Result::ok)
-            .filter(|entry| entry.path().extension() == Some(std::ffi::OsStr::new("java")))
-            .map(|entry| entry.path().display().to_string())
-            .collect(),
-        Err(e) => return Err(MatchMakerError::IOError(e))
-    };
-    
-    if java_files.is_empty() {
-        return Err(MatchMakerError::IOError(std::io::Error::new(std::io::ErrorKind::NotFound, "No Java files found")));
+/// Returns whether a compilation failed because of the bot's source, e.g. the compiler
+/// rejected it or the ZIP file is broken, so it would fail the same way again.
+fn is_source_error(error: &MatchMakerError) -> bool {
+    match error {
+        MatchMakerError::ZippingError(ZipError::Io(_)) => false,
+        MatchMakerError::CompileError(_)
+        | MatchMakerError::PlayerFileMissing
+        | MatchMakerError::MainMethodNotInPlayerFile
+        | MatchMakerError::UnsafeZip(_)
+        | MatchMakerError::ZippingError(_) => true,
+        _ => false,
     }
+}
 
-    // Check if "Player.java" exists in the list of Java files
-    if !java_files.iter().any(|file| file.ends_with("Player.java")) {
-        return Err(MatchMakerError::PlayerFileMissing);
+/// Checks whether a bot compiles like `compile_bot`, without storing anything.
+///
+/// Bots that failed to compile before fail again and compiled bots are taken as they are. Other
+/// bots are compiled in a temporary directory, which is removed afterwards.
+fn try_compile_bot(bot: &Bot, language: &str) -> Result<(), MatchMakerError> {
+    if bot.compile_status.eq("failed") && bot.compile_language.eq(language) {
+        return Err(MatchMakerError::CompileError(bot.compile_error.trim().to_string()));
     }
-    
-    // Convert the list of file paths to a format suitable for the `javac` command.
-    let java_files_str: Vec<&str> = java_files
-        .iter()
-        .map(AsRef::as_ref)
-        .collect();
 
-    // Player.java path
-    let player_java_path = java_files.iter().find(|&file| file.contains("Player.java")).cloned().unwrap();
-    let contains_main_method_option = contains_main_method(&player_java_path);
-    if let Ok(has_main_function) = contains_main_method_option {
-        if !has_main_function {
-            return Err(MatchMakerError::MainMethodNotInPlayerFile);
-        }
+    let hash = if bot.source_hash.is_empty() {
+        sha256_file(Path::new(&bot.source_path)).map_err(MatchMakerError::IOError)?
     } else {
-        return Err(MatchMakerError::MainMethodNotInPlayerFile);
+        bot.source_hash.clone()
+    };
+    let key = artifact_key(&hash, language);
+    if Path::new("./resources/workdir/artifacts").join(&key).is_dir() {
+        return Ok(());
     }
 
+    let workdir = Path::new("./resources/workdir/artifacts").join(format!("{}.{}.preview.tmp", key, bot.id));
+    let _ = fs::remove_dir_all(&workdir);
+    let result = compile_source(Path::new(&bot.source_path), &workdir, language);
+    let _ = fs::remove_dir_all(&workdir);
+    result.map_err(|e| MatchMakerError::CompileError(e.to_string().replace(&format!("{}/", workdir.display()), "")))
+}
 
-    // Compile the Java files.
-    if let Err(e) = execute_command(
-        "javac".to_string(),
-        java_files_str
-    ) {
-        return Err(MatchMakerError::IOError(e));
-    }
+/// Returns the name of a bot's compiled files, by the hash of its source and the language of
+/// its competition.
+fn artifact_key(hash: &str, language: &str) -> String {
+    if language.is_empty() { hash.to_string() } else { format!("{}-{}", hash, language) }
+}
 
-    Ok(())
+/// Returns the language of the competition a bot was uploaded to, empty if it is detected.
+pub fn competition_language(bot: &Bot) -> Result<String, MatchMakerError> {
+    let team = get_team_by_id(bot.team_id.clone()).map_err(MatchMakerError::DatabaseError)?;
+    let competition = get_competition_by_id(team.competition_id).map_err(MatchMakerError::DatabaseError)?;
+    Ok(competition.language)
+}
+
+fn store_compile_result(bot: &Bot, hash: String, language: &str, status: &str, error: String, diagnostics: Vec<CompileDiagnostic>) {
+    let diagnostics = serde_json::to_string(&diagnostics).unwrap_or("[]".to_string());
+    if let Err(e) = set_bot_compile_result(bot.id.clone(), hash, language.to_string(), status.to_string(), error, diagnostics) {
+        eprintln!("Error on storing compile result of bot {}: {:?}", bot.id, e);
+    }
 }
 
-/// Creates match pairs for a set of teams.
+/// Compiles the source code of an uploaded bot.
+///
+/// This function performs the following tasks:
+/// 1. Creates the working directory.
+/// 2. Unzips the bot's ZIP file into it, see `extract_zip`. If the ZIP holds a single folder,
+///    its contents are moved up.
+/// 3. Chooses the bot's language runtime from its `bot.json`, the competition's language or its
+///    sources, see `select_runtime`.
+/// 4. Finds the bot's entrypoint and compiles the bot with the runtime.
+/// 5. Adds a `Player` class running the bot if the runtime starts it with another command than
+///    `java Player`, since the evaluator starts every bot that way (see `write_launcher`).
 ///
 /// # Arguments
 ///
-/// * `match_num` - The number of matches each team should play.
-/// * `teams` - A vector containing all the teams.
+/// * `source_path` - The path of the uploaded ZIP file.
+/// * `workdir` - The directory the bot is compiled in.
+/// * `language` - The language of the bot's competition, empty to detect it.
 ///
 /// # Returns
 ///
-/// A vector containing tuples, where each tuple represents a match between two teams.
+/// * `Ok(())` if the bot's source code was compiled successfully.
+/// * `Err(MatchMakerError)` if any step in the process fails.
 ///
-/// # Panics
+/// # Errors
 ///
-/// The function may panic if the random number generation fails.
+/// This function will return an error if:
+/// * The working directory cannot be created.
+/// * The ZIP file cannot be unzipped, or is rejected as unsafe (e.g. an entry has a path
+///   outside the working directory).
+/// * The language is unknown or no sources of a known language are found.
+/// * The bot has no entrypoint, e.g. a Java bot without main class.
+/// * The bot cannot be compiled.
 /// 
-fn create_match_pairs(match_num: i32, teams: Vec<Team>) -> Vec<(Team, Team)> {
-    let mut pairs = Vec::new();
-    let games_to_play = ((teams.len() as f32 * match_num as f32) / 2.).ceil() as i32;
-
-    let mut players: Vec<usize> = std::iter::repeat(0..teams.len())
-        .take(match_num as usize)
-        .flatten()
-        .collect();
-
-    while (pairs.len() as i32) < games_to_play {
-        let random_index = rand::thread_rng().gen_range(0..players.len());
-        let first_team_index = players.swap_remove(random_index);
-    
-        if players.len() < 1 {
-            break
-        }
+fn compile_source(source_path: &Path, workdir: &Path, language: &str) -> Result<(), MatchMakerError> {
+    // Create a dedicated working directory for the bot.
+    if let Err(e) = fs::create_dir_all(workdir) {
+        return Err(MatchMakerError::IOError(e));
+    }
 
-        let random_index = rand::thread_rng().gen_range(0..players.len());
-        let second_team_index = players.swap_remove(random_index);
-    
-        pairs.push((
-            teams[first_team_index].clone(),
-            teams[second_team_index].clone(),
-        ));
+    // Unzip the bot's ZIP file in the working directory, rejecting unsafe ZIP files.
+    let source = fs::File::open(source_path).map_err(MatchMakerError::IOError)?;
+    let mut archive = ZipArchive::new(source).map_err(MatchMakerError::ZippingError)?;
+    extract_zip(&mut archive, workdir)?;
+    flatten_single_folder(workdir).map_err(MatchMakerError::IOError)?;
+
+    // Compile the bot with the runtime of its language
+    let manifest = read_manifest(workdir)?;
+    let runtime = select_runtime(workdir, &manifest, language)?;
+    let entrypoint = runtime.entrypoint(workdir, &manifest)?;
+    runtime.compile(workdir, &entrypoint)?;
+
+    let command = runtime.run_command(&entrypoint);
+    if !command.eq(&["java", PLAYER_CLASS]) {
+        write_launcher(workdir, &command)?;
     }
 
-    pairs
+    Ok(())
 }
\ No newline at end of file
@@ -4,4 +4,16 @@ pub mod matchmaker_2v2;
 pub mod command_executor;
 pub mod competitions;
 pub mod elo;
-pub mod file_handler;
\ No newline at end of file
+pub mod file_handler;
+pub mod leaderboard;
+pub mod pairing;
+pub mod schedule;
+pub mod final_evaluation;
+pub mod worker;
+pub mod worker_client;
+pub mod bracket;
+pub mod sandbox;
+pub mod java_sources;
+pub mod language;
+pub mod game_log;
+pub mod player_stats;
\ No newline at end of file
@@ -0,0 +1,525 @@
+use std::{cmp::Reverse, collections::HashMap};
+use rand::{RngCore, SeedableRng, rngs::StdRng, seq::SliceRandom};
+
+use crate::models::{competition::{Competition, PairingParams}, team::Team};
+
+/// Default maximal ELO difference between two teams paired by the `elo_window` strategy,
+/// as long as such an opponent is still available.
+const DEFAULT_ELO_WINDOW: i32 = 200;
+
+/// A way of pairing the teams of a competition for a round.
+pub trait PairingStrategy {
+    /// Pairs the teams for the given round of a competition.
+    ///
+    /// Returns pairs of indices into `teams`, one pair for each game to be played. Random
+    /// choices are taken from `rng`, so the same pairs are returned for the same seed.
+    fn pair(&self, round: i32, games_per_round: i32, teams: &[Team], rng: &mut dyn RngCore) -> Vec<(usize, usize)>;
+}
+
+/// Pairs teams with random opponents.
+pub struct RandomPairing;
+
+/// Pairs teams following a circle round-robin schedule that continues from round to round,
+/// so over enough rounds every team meets every other team (nearly) equally often.
+///
+/// With an odd number of teams one team sits out each round of the schedule. The teams that
+/// sat out during a round play each other instead, so every team plays `games_per_round`
+/// games. When the number of teams and the number of games per round are both odd, one of
+/// them plays an extra game against its neighbour in the schedule.
+pub struct RoundRobinPairing;
+
+/// Pairs teams with the available opponent closest in ELO.
+pub struct SwissPairing;
+
+/// Pairs teams with random opponents within an ELO window.
+pub struct EloWindowPairing {
+    pub window: i32,
+}
+
+impl PairingStrategy for RandomPairing {
+    fn pair(&self, _round: i32, games_per_round: i32, teams: &[Team], rng: &mut dyn RngCore) -> Vec<(usize, usize)> {
+        greedy_pairs(games_per_round, teams, false, rng, |_, _| 0)
+    }
+}
+
+impl PairingStrategy for SwissPairing {
+    fn pair(&self, _round: i32, games_per_round: i32, teams: &[Team], rng: &mut dyn RngCore) -> Vec<(usize, usize)> {
+        greedy_pairs(games_per_round, teams, true, rng, |team, candidate| (team.elo - candidate.elo).abs())
+    }
+}
+
+impl PairingStrategy for EloWindowPairing {
+    fn pair(&self, _round: i32, games_per_round: i32, teams: &[Team], rng: &mut dyn RngCore) -> Vec<(usize, usize)> {
+        greedy_pairs(games_per_round, teams, false, rng, |team, candidate| {
+            let distance = (team.elo - candidate.elo).abs();
+            if distance <= self.window { 0 } else { distance }
+        })
+    }
+}
+
+impl PairingStrategy for RoundRobinPairing {
+    fn pair(&self, round: i32, games_per_round: i32, teams: &[Team], _rng: &mut dyn RngCore) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        if teams.len() < 2 || games_per_round < 1 {
+            return pairs;
+        }
+
+        // the schedule has to be the same from round to round, so the teams are
+        // ordered by id instead of the order they were fetched in
+        let mut order: Vec<usize> = (0..teams.len()).collect();
+        order.sort_by(|a, b| teams[*a].id.cmp(&teams[*b].id));
+
+        let schedule_rounds = circle_rounds(order.len());
+        let mut sat_out: Vec<usize> = Vec::new();
+        for game in 0..games_per_round {
+            let schedule_round = (round * games_per_round + game) as usize % schedule_rounds.len();
+            sat_out.extend((0..order.len()).filter(|position| !schedule_rounds[schedule_round]
+                .iter()
+                .any(|(first, second)| first == position || second == position)));
+            for (first, second) in schedule_rounds[schedule_round].iter() {
+                // alternate the seats between cycles of the schedule
+                let cycle = (round * games_per_round + game) as usize / schedule_rounds.len();
+                if cycle % 2 == 1 {
+                    pairs.push((order[*second], order[*first]));
+                } else {
+                    pairs.push((order[*first], order[*second]));
+                }
+            }
+        }
+
+        // the teams that sat out are paired in the order they sat out
+        let mut waiting: Vec<usize> = Vec::new();
+        for position in sat_out.into_iter() {
+            match waiting.iter().position(|other| *other != position) {
+                Some(index) => {
+                    let other = waiting.remove(index);
+                    pairs.push((order[other], order[position]));
+                },
+                None => waiting.push(position),
+            }
+        }
+        for position in waiting.into_iter() {
+            pairs.push((order[position], order[(position + 1) % order.len()]));
+        }
+        pairs
+    }
+}
+
+/// Returns the pairing strategy with the given name, configured with the given parameters.
+///
+/// Known strategies are `random`, `round_robin`, `swiss` and `elo_window`. `None` is
+/// returned for unknown names.
+pub fn pairing_strategy(name: &str, params: &PairingParams) -> Option<Box<dyn PairingStrategy>> {
+    match name {
+        "random" => Some(Box::new(RandomPairing)),
+        "round_robin" => Some(Box::new(RoundRobinPairing)),
+        "swiss" => Some(Box::new(SwissPairing)),
+        "elo_window" => Some(Box::new(EloWindowPairing {
+            window: params.window.unwrap_or(DEFAULT_ELO_WINDOW),
+        })),
+        _ => None,
+    }
+}
+
+/// Creates match pairs for a round of a competition.
+///
+/// The pairing strategy and its parameters are read from the competition, falling back to
+/// random pairing if the competition's strategy is unknown or its parameters are invalid.
+/// Random choices are seeded with the competition and its round, so the pairs of a round
+/// are the same every time they are created for the same teams (e.g. for a preview).
+///
+/// # Arguments
+///
+/// * `competition` - The competition the round is played in.
+/// * `teams` - The teams that play in the round.
+///
+/// # Returns
+///
+/// A vector containing tuples, where each tuple represents a match between two teams.
+///
+pub fn create_match_pairs(competition: &Competition, teams: Vec<Team>) -> Vec<(Team, Team)> {
+    let params = serde_json::from_str::<PairingParams>(&competition.pairing_params)
+        .ok()
+        .filter(PairingParams::is_valid);
+    let strategy: Box<dyn PairingStrategy> = match params {
+        Some(params) => match pairing_strategy(&competition.pairing_strategy, &params) {
+            Some(strategy) => strategy,
+            None => {
+                eprintln!("Warning: unknown pairing strategy {} of competition {}, pairing randomly",
+                    competition.pairing_strategy, competition.id);
+                Box::new(RandomPairing)
+            },
+        },
+        None => {
+            eprintln!("Warning: invalid pairing parameters {} of competition {}, pairing randomly",
+                competition.pairing_params, competition.id);
+            Box::new(RandomPairing)
+        },
+    };
+
+    // the teams are ordered by id, the order they were fetched in may change
+    let mut teams = teams;
+    teams.sort_by(|a, b| a.id.cmp(&b.id));
+    let mut rng = StdRng::seed_from_u64(pairing_seed(competition));
+    strategy
+        .pair(competition.round, competition.games_per_round, &teams, &mut rng)
+        .into_iter()
+        .map(|(first, second)| (teams[first].clone(), teams[second].clone()))
+        .collect()
+}
+
+/// Returns the seed of the pairings of a competition's current round (FNV-1a of its ID and
+/// round, which does not change between builds like the standard library's hasher may).
+fn pairing_seed(competition: &Competition) -> u64 {
+    let round = competition.round.to_le_bytes();
+    competition.id
+        .as_bytes()
+        .iter()
+        .chain(round.iter())
+        .fold(0xcbf29ce484222325, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3))
+}
+
+/// Builds the rounds of a circle round-robin schedule for `team_count` teams.
+///
+/// With an odd number of teams one team sits out each round.
+fn circle_rounds(team_count: usize) -> Vec<Vec<(usize, usize)>> {
+    // a "ghost" team is added for odd counts, whoever is paired with it sits out
+    let slots = team_count + team_count % 2;
+    let mut rounds = Vec::new();
+    for schedule_round in 0..slots - 1 {
+        // the first slot stays in place while the others rotate
+        let mut arrangement: Vec<usize> = vec![0];
+        arrangement.extend((0..slots - 1).map(|i| 1 + (i + schedule_round) % (slots - 1)));
+
+        let round_pairs = (0..slots / 2)
+            .map(|i| (arrangement[i], arrangement[slots - 1 - i]))
+            .filter(|(first, second)| *first < team_count && *second < team_count)
+            .collect();
+        rounds.push(round_pairs);
+    }
+    rounds
+}
+
+/// Greedily pairs teams so that:
+/// - a team is never paired against itself,
+/// - a team is only paired with an opponent it already met in the round when every other
+///   available opponent was met at least as many times, and opponents are swapped between
+///   the pairs of a pass when that avoids meeting again,
+/// - every team plays exactly `games_per_round` games. When the number of teams and the
+///   number of games per round are both odd, one team plays an extra game so nobody is
+///   left a game short.
+///
+/// Among the opponents met the fewest times, the one with the smallest `distance` is picked
+/// (ties are broken randomly). Teams pick their opponents by descending ELO if `by_elo` is
+/// set, otherwise in random order.
+fn greedy_pairs(
+    games_per_round: i32,
+    teams: &[Team],
+    by_elo: bool,
+    rng: &mut dyn RngCore,
+    distance: impl Fn(&Team, &Team) -> i32,
+) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    if teams.len() < 2 || games_per_round < 1 {
+        return pairs;
+    }
+
+    let mut remaining: Vec<i32> = vec![games_per_round; teams.len()];
+    let mut meetings: HashMap<(usize, usize), i32> = HashMap::new();
+
+    // every pass gives each team at most one game
+    loop {
+        let mut order: Vec<usize> = (0..teams.len())
+            .filter(|team| remaining[*team] > 0)
+            .collect();
+        if order.len() < 2 {
+            break;
+        }
+
+        if by_elo {
+            order.sort_by_key(|team| Reverse(teams[*team].elo));
+        } else {
+            order.shuffle(rng);
+        }
+        // teams that still need the most games pick first (the sort is stable, so the
+        // strategy's order is kept among them), otherwise a team could be left without
+        // opponents at the end of the round
+        order.sort_by_key(|team| Reverse(remaining[*team]));
+
+        let mut paired = vec![false; teams.len()];
+        let mut pass_pairs = Vec::new();
+        for team in order.iter() {
+            if paired[*team] {
+                continue;
+            }
+            let candidates: Vec<usize> = order
+                .iter()
+                .copied()
+                .filter(|candidate| *candidate != *team && !paired[*candidate])
+                .collect();
+            if let Some(opponent) = pick_opponent(*team, &candidates, &meetings, teams, &distance, rng) {
+                paired[*team] = true;
+                paired[opponent] = true;
+                pass_pairs.push((*team, opponent));
+            }
+        }
+        // the last teams of a pass may only have opponents left they already met
+        swap_repeated_meetings(&mut pass_pairs, &meetings);
+        for (team, opponent) in pass_pairs.into_iter() {
+            record_pair(team, opponent, &mut remaining, &mut meetings, &mut pairs);
+        }
+    }
+
+    // an odd number of game slots leaves one team short, it gets paired with
+    // one of the others for an extra game
+    if let Some(team) = (0..teams.len()).find(|team| remaining[*team] > 0) {
+        let candidates: Vec<usize> = (0..teams.len()).filter(|candidate| *candidate != team).collect();
+        while remaining[team] > 0 {
+            match pick_opponent(team, &candidates, &meetings, teams, &distance, rng) {
+                Some(opponent) => record_pair(team, opponent, &mut remaining, &mut meetings, &mut pairs),
+                None => break,
+            }
+        }
+    }
+
+    pairs
+}
+
+/// Swaps the opponents of two pairs as long as that lowers how often the teams of the pairs
+/// already met.
+fn swap_repeated_meetings(pairs: &mut [(usize, usize)], meetings: &HashMap<(usize, usize), i32>) {
+    let met = |first: usize, second: usize| meetings.get(&meeting_key(first, second)).copied().unwrap_or(0);
+    let mut swapped = true;
+    while swapped {
+        swapped = false;
+        for i in 0..pairs.len() {
+            for j in i + 1..pairs.len() {
+                let ((first, second), (third, fourth)) = (pairs[i], pairs[j]);
+                let current = met(first, second) + met(third, fourth);
+                if met(first, third) + met(second, fourth) < current {
+                    pairs[i] = (first, third);
+                    pairs[j] = (second, fourth);
+                    swapped = true;
+                } else if met(first, fourth) + met(second, third) < current {
+                    pairs[i] = (first, fourth);
+                    pairs[j] = (second, third);
+                    swapped = true;
+                }
+            }
+        }
+    }
+}
+
+fn pick_opponent(
+    team: usize,
+    candidates: &[usize],
+    meetings: &HashMap<(usize, usize), i32>,
+    teams: &[Team],
+    distance: &impl Fn(&Team, &Team) -> i32,
+    rng: &mut dyn RngCore,
+) -> Option<usize> {
+    candidates
+        .iter()
+        .copied()
+        .min_by_key(|candidate| {
+            let met = meetings.get(&meeting_key(team, *candidate)).copied().unwrap_or(0);
+            (met, distance(&teams[team], &teams[*candidate]), rng.next_u32())
+        })
+}
+
+fn record_pair(
+    first: usize,
+    second: usize,
+    remaining: &mut [i32],
+    meetings: &mut HashMap<(usize, usize), i32>,
+    pairs: &mut Vec<(usize, usize)>,
+) {
+    remaining[first] -= 1;
+    remaining[second] -= 1;
+    *meetings.entry(meeting_key(first, second)).or_insert(0) += 1;
+    pairs.push((first, second));
+}
+
+fn meeting_key(first: usize, second: usize) -> (usize, usize) {
+    if first < second { (first, second) } else { (second, first) }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::NaiveDateTime;
+
+    use super::*;
+
+    /// Teams ordered by id, with ELOs 25 apart.
+    fn teams(count: usize) -> Vec<Team> {
+        (0..count)
+            .map(|i| Team {
+                id: format!("team-{:02}", i),
+                name: format!("Team {}", i),
+                owner: String::new(),
+                partner: String::new(),
+                competition_id: "competition".to_string(),
+                bot1: String::new(),
+                bot2: String::new(),
+                elo: 1000 + 25 * i as i32,
+                created: NaiveDateTime::default(),
+            })
+            .collect()
+    }
+
+    fn competition(strategy: &str, round: i32, games_per_round: i32) -> Competition {
+        Competition {
+            id: "competition".to_string(),
+            name: "Competition".to_string(),
+            start: NaiveDateTime::default(),
+            end: NaiveDateTime::default(),
+            allowed_submissions: true,
+            round,
+            type_: "2v2".to_string(),
+            games_per_round,
+            game_pack: String::new(),
+            created: NaiveDateTime::default(),
+            pairing_strategy: strategy.to_string(),
+            pairing_params: "{}".to_string(),
+            schedule: String::new(),
+            sandbox_limits: "{}".to_string(),
+            match_timeout: 300,
+            timeout_policy: String::new(),
+            language: String::new(),
+            upload_quota: 20,
+            max_upload_mb: 10,
+        }
+    }
+
+    fn strategies() -> Vec<(&'static str, Box<dyn PairingStrategy>)> {
+        ["random", "round_robin", "swiss", "elo_window"]
+            .into_iter()
+            .map(|name| (name, pairing_strategy(name, &PairingParams { window: Some(50) }).unwrap()))
+            .collect()
+    }
+
+    fn games_per_team(pairs: &[(usize, usize)], team_count: usize) -> Vec<i32> {
+        let mut games = vec![0; team_count];
+        for (first, second) in pairs.iter() {
+            games[*first] += 1;
+            games[*second] += 1;
+        }
+        games
+    }
+
+    fn meetings(pairs: &[(usize, usize)]) -> HashMap<(usize, usize), i32> {
+        let mut meetings = HashMap::new();
+        for (first, second) in pairs.iter() {
+            *meetings.entry(meeting_key(*first, *second)).or_insert(0) += 1;
+        }
+        meetings
+    }
+
+    #[test]
+    fn no_team_plays_itself() {
+        for (name, strategy) in strategies() {
+            for team_count in 2..=9 {
+                for games_per_round in 1..=5 {
+                    for round in 0..4 {
+                        let mut rng = StdRng::seed_from_u64(round as u64);
+                        let pairs = strategy.pair(round, games_per_round, &teams(team_count), &mut rng);
+                        assert!(pairs.iter().all(|(first, second)| first != second),
+                            "{} paired a team with itself: {:?}", name, pairs);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_team_plays_games_per_round() {
+        for (name, strategy) in strategies() {
+            for team_count in 2..=9 {
+                for games_per_round in 1..=5 {
+                    for round in 0..4 {
+                        let mut rng = StdRng::seed_from_u64(round as u64);
+                        let pairs = strategy.pair(round, games_per_round, &teams(team_count), &mut rng);
+                        let games = games_per_team(&pairs, team_count);
+                        let extra = games.iter().filter(|played| **played == games_per_round + 1).count();
+                        let odd_slots = team_count as i32 * games_per_round % 2 == 1;
+                        assert_eq!(extra, odd_slots as usize, "{} with {} teams and {} games: {:?}",
+                            name, team_count, games_per_round, games);
+                        assert_eq!(games.iter().filter(|played| **played == games_per_round).count(),
+                            team_count - extra, "{} with {} teams and {} games: {:?}",
+                            name, team_count, games_per_round, games);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn round_robin_meets_every_team() {
+        for team_count in 2..=9 {
+            // the schedule has a round per team with an odd count, one less with an even count
+            let schedule_length = team_count - 1 + team_count % 2;
+            let mut pairs = Vec::new();
+            for round in 0..schedule_length as i32 {
+                pairs.extend(RoundRobinPairing.pair(round, 1, &teams(team_count), &mut StdRng::seed_from_u64(0)));
+            }
+            let meetings = meetings(&pairs);
+            for first in 0..team_count {
+                for second in first + 1..team_count {
+                    let met = meetings.get(&(first, second)).copied().unwrap_or(0);
+                    if team_count % 2 == 0 {
+                        assert_eq!(met, 1, "{} teams: {} and {} met {} times", team_count, first, second, met);
+                    } else {
+                        assert!(met >= 1, "{} teams: {} and {} never met", team_count, first, second);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn greedy_pairs_avoid_repeated_meetings() {
+        for (name, strategy) in strategies().into_iter().filter(|(name, _)| *name != "round_robin") {
+            for team_count in 3..=10 {
+                for games_per_round in 1..team_count as i32 {
+                    for seed in 0..8 {
+                        let mut rng = StdRng::seed_from_u64(seed);
+                        let pairs = strategy.pair(0, games_per_round, &teams(team_count), &mut rng);
+                        let most = meetings(&pairs).values().copied().max().unwrap_or(0);
+                        // with an even count, every team can meet half of the others once
+                        if team_count % 2 == 0 && games_per_round as usize <= team_count / 2 {
+                            assert_eq!(most, 1, "{} with {} teams and {} games repeated a meeting: {:?}",
+                                name, team_count, games_per_round, pairs);
+                        } else {
+                            assert!(most <= 2, "{} with {} teams and {} games met {} times: {:?}",
+                                name, team_count, games_per_round, most, pairs);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn seeded_pairing_is_deterministic() {
+        for strategy in ["random", "round_robin", "swiss", "elo_window"] {
+            let competition = competition(strategy, 3, 3);
+            let mut reversed = teams(7);
+            reversed.reverse();
+            let ids = |pairs: Vec<(Team, Team)>| pairs
+                .into_iter()
+                .map(|(first, second)| (first.id, second.id))
+                .collect::<Vec<_>>();
+            assert_eq!(ids(create_match_pairs(&competition, teams(7))), ids(create_match_pairs(&competition, reversed)),
+                "{} pairs depend on the order of the teams", strategy);
+        }
+    }
+
+    #[test]
+    fn unknown_strategy_pairs_randomly() {
+        let pairs = create_match_pairs(&competition("unknown", 0, 2), teams(6));
+        assert_eq!(pairs.len(), 6);
+        assert!(pairs.iter().all(|(first, second)| first.id != second.id));
+    }
+}
@@ -0,0 +1,38 @@
+use diesel::result::Error;
+
+use crate::{
+    db::{operations_game2v2::get_games_page, operations_game_player_stats::insert_player_stats},
+    models::game_player_stats::SqlGamePlayerStats,
+};
+
+/// Number of games read from the database at once while backfilling.
+const BACKFILL_BATCH_SIZE: i64 = 500;
+
+/// Stores the player statistics of all games, read from their `additional_data`.
+///
+/// Games stored before the `game_player_stats` table existed have no statistics there. Running
+/// this again is safe, the statistics of a game are replaced.
+///
+/// # Returns
+///
+/// The number of games read and the number of statistics stored, or a diesel `Error`. Batches
+/// stored before the error are kept.
+///
+pub fn backfill_player_stats() -> Result<(usize, usize), Error> {
+    let mut games_read = 0;
+    let mut stats_stored = 0;
+    loop {
+        let games = get_games_page(games_read as i64, BACKFILL_BATCH_SIZE)?;
+        if games.is_empty() {
+            return Ok((games_read, stats_stored));
+        }
+        games_read += games.len();
+
+        let stats: Vec<SqlGamePlayerStats> = games
+            .iter()
+            .flat_map(SqlGamePlayerStats::from_game)
+            .collect();
+        stats_stored += stats.len();
+        insert_player_stats(stats)?;
+    }
+}
@@ -0,0 +1,391 @@
+use std::{env, ffi::CString, fs, io, os::unix::{ffi::OsStrExt, process::CommandExt}, path::{Path, PathBuf}, process::Command, sync::atomic::{AtomicBool, Ordering}, thread, time::Duration};
+
+use crate::models::{competition::{Competition, SandboxLimits}, errors::MatchMakerError};
+
+/// Set once the missing cgroup was logged, so it is not logged for every game.
+static WARNED: AtomicBool = AtomicBool::new(false);
+
+const MIB: u64 = 1024 * 1024;
+
+/// The environment of the processes in the sandbox, `JAVA_HOME` is passed on if it is set.
+const SANDBOX_ENV: [(&str, &str); 3] = [
+    ("PATH", "/usr/local/bin:/usr/bin:/bin:/usr/local/sbin:/usr/sbin:/sbin"),
+    ("HOME", "/tmp"),
+    ("LANG", "C.UTF-8"),
+];
+
+/// The system paths the runtimes of a game (the JVM, Python, g++ binaries and the libraries
+/// they load) come from. They are bound read-only into the sandbox if they exist.
+const SYSTEM_PATHS: [&str; 10] = [
+    "/usr", "/bin", "/sbin", "/lib", "/lib32", "/lib64",
+    "/etc/alternatives", "/etc/ld.so.cache", "/etc/ld.so.conf", "/etc/ld.so.conf.d",
+];
+
+/// Returns the sandbox limits of a competition, falling back to the default limits if the
+/// competition's limits are invalid.
+pub fn sandbox_limits(competition: &Competition) -> SandboxLimits {
+    serde_json::from_str::<SandboxLimits>(&competition.sandbox_limits)
+        .ok()
+        .filter(SandboxLimits::is_valid)
+        .unwrap_or_default()
+}
+
+/// The sandbox a game is played in, or a bot is compiled in.
+///
+/// Processes started with `command` (and all of their children) run in bubblewrap (`bwrap`) with:
+/// - A file system holding only the runtimes (`SYSTEM_PATHS`, the JVM configuration in
+///   `/etc/java*` and `$JAVA_HOME`) and the resources of the game (e.g. the evaluator), all
+///   read-only, the match folder (their private scratch directory) and a private `/tmp`.
+///   Nothing else of the host is visible, neither the dashboard's `.env` nor other games.
+/// - None of the dashboard's environment variables (e.g. `DATABASE_URL`), only `SANDBOX_ENV`.
+/// - A private network namespace, only the loopback interface is available. Bots can still
+///   talk to the evaluator, but not to the database, LDAP or anything else.
+/// - Rlimits on CPU time, open files and file size per process.
+/// - A cgroup (v2) limiting the memory and process count of the whole game, if the
+///   `SANDBOX_CGROUP` environment variable points to a cgroup the dashboard may create child
+///   groups in, with the `memory` and `pids` controllers enabled for its children. Without it,
+///   memory is limited per process with `RLIMIT_AS`, and `RLIMIT_NPROC` limits the processes
+///   the game may start. `RLIMIT_NPROC` counts all processes and threads of the user, so the
+///   game may start `max_processes` on top of the ones the user runs when the game starts
+///   (games started at the same time may share their allowance).
+///
+/// Every command runs in its own process group, so the processes of a game can be terminated
+/// without touching the ones of games played in parallel, see `terminate`.
+///
+/// A missing cgroup is logged once and the game is played anyway. The cgroup is removed,
+/// killing whatever is left in it, when the sandbox is dropped.
+pub struct Sandbox {
+    limits: SandboxLimits,
+    scratch: PathBuf,
+    resources: Vec<PathBuf>,
+    cgroup: Option<PathBuf>,
+}
+
+impl Sandbox {
+    /// Prepares the sandbox of a game.
+    ///
+    /// # Arguments
+    ///
+    /// * `match_folder` - The folder of the game, the only directory its processes may write to.
+    /// * `resources` - The files and directories the game's processes may read besides the
+    ///   runtimes, e.g. the evaluator. They are visible under their full path.
+    /// * `name` - The name of the game's cgroup, e.g. the game ID.
+    /// * `limits` - The limits of the game's processes.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MatchMakerError::SandboxError` if the network of the sandbox cannot be isolated
+    /// (e.g. bwrap is not installed), the game must not be played then. Returns a
+    /// `MatchMakerError::IOError` if the match folder or a resource does not exist, or the cgroup
+    /// is configured but cannot be set up.
+    ///
+    pub fn new(match_folder: &Path, resources: &[&Path], name: &str, limits: SandboxLimits) -> Result<Sandbox, MatchMakerError> {
+        let scratch = fs::canonicalize(match_folder).map_err(MatchMakerError::IOError)?;
+        let resources = resources
+            .iter()
+            .map(fs::canonicalize)
+            .collect::<Result<Vec<PathBuf>, io::Error>>()
+            .map_err(MatchMakerError::IOError)?;
+        check_network_isolation(&scratch)?;
+
+        let cgroup = match env::var("SANDBOX_CGROUP") {
+            Ok(root) if !root.is_empty() => Some(
+                create_cgroup(Path::new(&root), name, &limits).map_err(MatchMakerError::IOError)?
+            ),
+            _ => None,
+        };
+        if cgroup.is_none() && !WARNED.swap(true, Ordering::SeqCst) {
+            eprintln!("$SANDBOX_CGROUP is not set, memory is limited per process and the process count per user");
+        }
+
+        Ok(Sandbox { limits, scratch, resources, cgroup })
+    }
+
+    /// Creates a command running `program` with `args` inside the sandbox.
+    pub fn command(&self, program: &str, args: &[String]) -> Command {
+        let mut command = bwrap(&self.scratch, &self.resources);
+        command.arg(program).args(args);
+
+        let cgroup_procs = self.cgroup
+            .as_ref()
+            .and_then(|cgroup| CString::new(cgroup.join("cgroup.procs").as_os_str().as_bytes()).ok());
+        let cpu_seconds = self.limits.cpu_seconds;
+        let max_open_files = self.limits.max_open_files;
+        let max_file_size = self.limits.max_file_size_mb * MIB;
+        let memory = self.limits.memory_mb * MIB;
+        let max_processes = match self.cgroup {
+            Some(_) => 0,
+            None => user_process_count() + self.limits.max_processes,
+        };
+
+        // only async-signal-safe calls are allowed between fork and exec, so nothing is
+        // allocated in here
+        unsafe {
+            command.pre_exec(move || {
+                if libc::setpgid(0, 0) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                if let Some(procs) = &cgroup_procs {
+                    join_cgroup(procs)?;
+                } else {
+                    set_rlimit(libc::RLIMIT_AS, memory)?;
+                    set_rlimit(libc::RLIMIT_NPROC, max_processes)?;
+                }
+                set_rlimit(libc::RLIMIT_CPU, cpu_seconds)?;
+                set_rlimit(libc::RLIMIT_NOFILE, max_open_files)?;
+                set_rlimit(libc::RLIMIT_FSIZE, max_file_size)
+            });
+        }
+        command
+    }
+
+    /// Kills the processes of a command started in the sandbox, along with everything left in
+    /// the sandbox's cgroup.
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - The process ID of the command, which is also its process group ID.
+    ///
+    /// # Returns
+    ///
+    /// The number of processes started by the command that were still running, e.g. the bots
+    /// of a game that timed out. bwrap and the command itself are not counted. Processes left
+    /// by a command that exited are already gone, bwrap's PID namespace ends with the command.
+    ///
+    pub fn terminate(&self, group: u32) -> usize {
+        let mut pids = process_group_members(group);
+        if let Some(cgroup) = &self.cgroup {
+            pids.extend(cgroup_members(cgroup));
+        }
+        pids.sort();
+        pids.dedup();
+
+        // bwrap runs the command as the child of its own init process, everything else was
+        // started by the command
+        let stats: Vec<(u32, ProcessStat)> = pids
+            .into_iter()
+            .filter_map(|pid| process_stat(pid).map(|stat| (pid, stat)))
+            .collect();
+        let bwrap: Vec<u32> = stats
+            .iter()
+            .filter(|(pid, stat)| *pid == group || stat.name.eq("bwrap"))
+            .map(|(pid, _)| *pid)
+            .collect();
+        let started = stats
+            .iter()
+            .filter(|(pid, stat)| !bwrap.contains(pid) && !bwrap.contains(&stat.parent))
+            .count();
+
+        unsafe {
+            libc::killpg(group as libc::pid_t, libc::SIGKILL);
+        }
+        if let Some(cgroup) = &self.cgroup {
+            let _ = fs::write(cgroup.join("cgroup.kill"), "1");
+        }
+        started
+    }
+}
+
+impl Drop for Sandbox {
+    fn drop(&mut self) {
+        if let Some(cgroup) = &self.cgroup {
+            if let Err(e) = remove_cgroup(cgroup) {
+                eprintln!("Failed removing cgroup {:?}: {:?}", cgroup, e);
+            }
+        }
+    }
+}
+
+/// Creates a bwrap command with the isolation of the sandbox, the command to run inside has
+/// to be appended. It runs in the scratch directory.
+fn bwrap(scratch: &Path, resources: &[PathBuf]) -> Command {
+    let mut command = Command::new("bwrap");
+    command.env_clear().envs(SANDBOX_ENV);
+    if let Some(java_home) = env::var_os("JAVA_HOME") {
+        command.env("JAVA_HOME", java_home);
+    }
+    for path in runtime_paths().iter().chain(resources) {
+        command.arg("--ro-bind-try").arg(path).arg(path);
+    }
+    command
+        .args(["--dev", "/dev", "--unshare-pid", "--proc", "/proc", "--tmpfs", "/tmp"])
+        .arg("--unshare-net")
+        .arg("--bind").arg(scratch).arg(scratch)
+        .arg("--chdir").arg(scratch)
+        .args(["--die-with-parent", "--"]);
+    command
+}
+
+/// Returns the paths of the runtimes a game may use, see `SYSTEM_PATHS`.
+fn runtime_paths() -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = SYSTEM_PATHS.iter().map(PathBuf::from).collect();
+    // the configuration of the installed JVMs, e.g. /etc/java-17-openjdk
+    if let Ok(entries) = fs::read_dir("/etc") {
+        paths.extend(entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with("java"))
+            .map(|entry| entry.path()));
+    }
+    if let Some(java_home) = env::var_os("JAVA_HOME").filter(|home| !home.is_empty()) {
+        paths.push(PathBuf::from(java_home));
+    }
+    paths
+}
+
+/// Checks that processes in the sandbox see no network interface besides loopback.
+fn check_network_isolation(scratch: &Path) -> Result<(), MatchMakerError> {
+    let output = bwrap(scratch, &[])
+        .args(["cat", "/proc/net/dev"])
+        .output()
+        .map_err(|e| MatchMakerError::SandboxError(format!("Failed running bwrap: {}", e)))?;
+    if !output.status.success() {
+        return Err(MatchMakerError::SandboxError(format!(
+            "Failed isolating the network: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    // the first two lines are headers, every other line starts with an interface name
+    let interfaces: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .skip(2)
+        .filter_map(|line| line.split(':').next())
+        .map(|interface| interface.trim().to_string())
+        .collect();
+    if interfaces.iter().any(|interface| interface.ne("lo")) {
+        return Err(MatchMakerError::SandboxError(format!(
+            "Network is not isolated, the sandbox sees the interfaces {:?}",
+            interfaces
+        )));
+    }
+    Ok(())
+}
+
+/// The fields of `/proc/{pid}/stat` the sandbox needs.
+struct ProcessStat {
+    name: String,
+    state: String,
+    parent: u32,
+    group: u32,
+}
+
+fn process_stat(pid: u32) -> Option<ProcessStat> {
+    // the command name in the second field may contain spaces and parentheses, the fields
+    // after it don't
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let (start, end) = (stat.find('(')?, stat.rfind(')')?);
+    let fields: Vec<&str> = stat[end + 1..].split_whitespace().collect();
+    if fields.len() < 3 {
+        return None;
+    }
+    // the state, the parent and the process group are the third to fifth field
+    Some(ProcessStat {
+        name: stat[start + 1..end].to_string(),
+        state: fields[0].to_string(),
+        parent: fields[1].parse().ok()?,
+        group: fields[2].parse().ok()?,
+    })
+}
+
+/// Returns the running processes (zombies left out) of a process group.
+fn process_group_members(group: u32) -> Vec<u32> {
+    let entries = match fs::read_dir("/proc") {
+        Ok(e) => e,
+        Err(_) => return Vec::new(),
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()))
+        .filter(|pid| process_stat(*pid)
+            .is_some_and(|stat| stat.state.ne("Z") && stat.state.ne("X") && stat.group == group))
+        .collect()
+}
+
+/// Returns the number of processes and threads of the user running the dashboard, which is
+/// what `RLIMIT_NPROC` is checked against.
+fn user_process_count() -> u64 {
+    let uid = unsafe { libc::getuid() }.to_string();
+    let entries = match fs::read_dir("/proc") {
+        Ok(e) => e,
+        Err(_) => return 0,
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_name().to_str().is_some_and(|name| name.parse::<u32>().is_ok()))
+        .map(|entry| {
+            let status = fs::read_to_string(entry.path().join("status")).unwrap_or_default();
+            let field = |name: &str| status
+                .lines()
+                .find_map(|line| line.strip_prefix(name))
+                .and_then(|value| value.split_whitespace().next().map(str::to_string));
+            // the real user ID is the first of the Uid field
+            match field("Uid:") {
+                Some(owner) if owner.eq(&uid) => field("Threads:").and_then(|t| t.parse::<u64>().ok()).unwrap_or(1),
+                _ => 0,
+            }
+        })
+        .sum()
+}
+
+/// Returns the processes in a cgroup.
+fn cgroup_members(cgroup: &Path) -> Vec<u32> {
+    fs::read_to_string(cgroup.join("cgroup.procs"))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.trim().parse::<u32>().ok())
+        .collect()
+}
+
+fn create_cgroup(root: &Path, name: &str, limits: &SandboxLimits) -> Result<PathBuf, io::Error> {
+    let cgroup = root.join(name);
+    if !cgroup.exists() {
+        fs::create_dir(&cgroup)?;
+    }
+    fs::write(cgroup.join("memory.max"), (limits.memory_mb * MIB).to_string())?;
+    fs::write(cgroup.join("pids.max"), limits.max_processes.to_string())?;
+    // swap is only limited if swap accounting is enabled
+    let _ = fs::write(cgroup.join("memory.swap.max"), "0");
+    Ok(cgroup)
+}
+
+fn remove_cgroup(cgroup: &Path) -> Result<(), io::Error> {
+    // cgroup.kill needs Linux 5.14, on older kernels the processes were killed by their group
+    let _ = fs::write(cgroup.join("cgroup.kill"), "1");
+
+    // the killed processes take a moment to leave the cgroup
+    let mut result = fs::remove_dir(cgroup);
+    for _ in 0..50 {
+        if result.is_ok() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(100));
+        result = fs::remove_dir(cgroup);
+    }
+    result
+}
+
+/// Moves the calling process into the cgroup whose `cgroup.procs` file is given.
+fn join_cgroup(procs: &CString) -> Result<(), io::Error> {
+    unsafe {
+        let fd = libc::open(procs.as_ptr(), libc::O_WRONLY);
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // writing 0 moves the writing process
+        let written = libc::write(fd, b"0\n".as_ptr() as *const libc::c_void, 2);
+        let error = io::Error::last_os_error();
+        libc::close(fd);
+        if written < 0 {
+            return Err(error);
+        }
+    }
+    Ok(())
+}
+
+fn set_rlimit(resource: libc::__rlimit_resource_t, value: u64) -> Result<(), io::Error> {
+    let limit = libc::rlimit { rlim_cur: value as libc::rlim_t, rlim_max: value as libc::rlim_t };
+    if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, Utc};
+use cron::Schedule;
+
+/// When the rounds of a competition are played.
+pub enum RoundSchedule {
+    /// A cron expression, e.g. `0 0 2 * * * *` for every night at 2am.
+    Cron(Schedule),
+    /// A fixed interval, written as `@every 10m` (units: `s`, `m`, `h` and `d`).
+    Every(Duration),
+}
+
+impl RoundSchedule {
+    /// Returns whether a round is due at `now`, given the time of the previous round.
+    pub fn is_due(&self, last_run: &DateTime<Utc>, now: &DateTime<Utc>) -> bool {
+        match self {
+            RoundSchedule::Cron(schedule) => schedule
+                .after(last_run)
+                .next()
+                .is_some_and(|next| next <= *now),
+            RoundSchedule::Every(interval) => *last_run + *interval <= *now,
+        }
+    }
+}
+
+/// Parses the schedule of a competition.
+///
+/// # Arguments
+///
+/// * `schedule` - Either a cron expression (with seconds, e.g. `0 */10 * * * * *`) or an
+///   interval like `@every 10m`.
+///
+/// # Returns
+///
+/// The parsed `RoundSchedule`, or `None` if the schedule is not valid.
+///
+pub fn parse_schedule(schedule: &str) -> Option<RoundSchedule> {
+    let schedule = schedule.trim();
+    if let Some(interval) = schedule.strip_prefix("@every") {
+        return parse_interval(interval.trim()).map(RoundSchedule::Every);
+    }
+    Schedule::from_str(schedule).ok().map(RoundSchedule::Cron)
+}
+
+fn parse_interval(interval: &str) -> Option<Duration> {
+    let unit = interval.chars().last()?;
+    let amount: i64 = interval[..interval.len() - unit.len_utf8()].parse().ok()?;
+    if amount < 1 {
+        return None;
+    }
+    match unit {
+        's' => Some(Duration::seconds(amount)),
+        'm' => Some(Duration::minutes(amount)),
+        'h' => Some(Duration::hours(amount)),
+        'd' => Some(Duration::days(amount)),
+        _ => None,
+    }
+}
@@ -0,0 +1,191 @@
+use std::{env, fs, path::Path};
+
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use chrono::{Duration, Local};
+
+use crate::{
+    db::{
+        operations_bot::get_bot_by_id,
+        operations_competition::get_competition_by_id,
+        operations_game2v2::get_game_by_id,
+        operations_match_job::{claim_match_job, get_match_job_by_id, set_match_job_status},
+        operations_worker::{get_worker_by_id, insert_worker, set_worker_seen}
+    },
+    models::{
+        errors::MatchMakerError,
+        match_job::MatchJob,
+        worker::{NewWorker, Worker, WorkerGameResult, WorkerMatchJob}
+    }
+};
+
+use super::{
+    file_handler::zip_directory,
+    sandbox::sandbox_limits,
+    matchmaker_2v2::{compile_bot, competition_language, finalize_2v2_round, replay_directory, save_game_errors, store_game}
+};
+
+/// Minutes after which a job claimed by a worker is handed out again, its worker is then
+/// considered gone. Match timeouts are at most eight minutes (see `MAX_MATCH_TIMEOUT`), so
+/// games are over by then.
+const WORKER_JOB_TIMEOUT_MINUTES: i64 = 10;
+
+/// Returns whether the match jobs are played by remote workers instead of the dashboard itself.
+///
+/// Remote workers are enabled by setting the `REMOTE_WORKERS` environment variable to `true`.
+pub fn remote_workers_enabled() -> bool {
+    env::var("REMOTE_WORKERS").is_ok_and(|enabled| enabled.eq("true"))
+}
+
+/// Returns whether the request was made with the shared worker token (the `WORKER_TOKEN`
+/// environment variable). Without the variable, no request is accepted as a worker.
+pub fn is_worker_token(auth: &BearerAuth) -> bool {
+    match env::var("WORKER_TOKEN") {
+        Ok(token) => !token.is_empty() && token.eq(auth.token()),
+        Err(_) => false,
+    }
+}
+
+pub fn register_worker(name: String) -> Result<Worker, MatchMakerError> {
+    match insert_worker(NewWorker { name }) {
+        Ok(w) => Ok(w),
+        Err(e) => Err(MatchMakerError::DatabaseError(e))
+    }
+}
+
+/// Claims the next match job for a worker.
+///
+/// # Arguments
+///
+/// * `worker_id` - The ID the worker got when registering.
+///
+/// # Returns
+///
+/// A `Result` containing the claimed job, or `None` if there is nothing to play right now.
+///
+/// # Errors
+///
+/// Returns a `MatchMakerError::DatabaseError` if the worker is not registered or the job
+/// could not be claimed.
+///
+pub fn claim_job(worker_id: String) -> Result<Option<WorkerMatchJob>, MatchMakerError> {
+    let worker = match get_worker_by_id(worker_id) {
+        Ok(w) => w,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+    if let Err(e) = set_worker_seen(worker.id.clone()) {
+        return Err(MatchMakerError::DatabaseError(e));
+    }
+
+    let stale_before = Local::now().naive_utc() - Duration::minutes(WORKER_JOB_TIMEOUT_MINUTES);
+    let job = match claim_match_job(worker.id, stale_before) {
+        Ok(Some(j)) => j,
+        Ok(None) => return Ok(None),
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+
+    let competition = match get_competition_by_id(job.competition_id.clone()) {
+        Ok(c) => c,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+    let mut worker_job = WorkerMatchJob::from(job);
+    worker_job.sandbox_limits = sandbox_limits(&competition);
+    worker_job.match_timeout = competition.match_timeout;
+    Ok(Some(worker_job))
+}
+
+/// Returns the compiled bot with the given ID, zipped.
+pub fn compiled_bot_archive(bot_id: String) -> Result<Vec<u8>, MatchMakerError> {
+    // only hand out directories of known bots
+    let bot = match get_bot_by_id(bot_id) {
+        Ok(b) => b,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+    zip_directory(&compile_bot(&bot, &competition_language(&bot)?)?)
+}
+
+/// Stores the result of a match job played by a worker and finalizes the round if it was
+/// the last job of the round.
+///
+/// Submitting a result again (e.g. the worker retried after losing the response) is accepted
+/// without storing anything, the game of the job is already stored then. Results of jobs that
+/// were settled without a game (e.g. the job failed in the meantime) are rejected.
+///
+/// # Arguments
+///
+/// * `job_id` - The ID of the played job.
+/// * `result` - The result parsed by the worker.
+/// * `replay_file` - The uploaded replay zip.
+/// * `errors` - The error output of the game.
+///
+/// # Errors
+///
+/// Returns a `MatchMakerError::JobSettled` if the job was settled without a game, or another
+/// `MatchMakerError` if the job does not exist, the replay or the game cannot be stored, or the
+/// round cannot be finalized.
+///
+pub fn submit_result(job_id: String, result: WorkerGameResult, replay_file: &Path, errors: Vec<String>) -> Result<(), MatchMakerError> {
+    let job = match get_match_job_by_id(job_id) {
+        Ok(j) => j,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+    if get_game_by_id(job.id.clone()).is_ok() {
+        return Ok(());
+    }
+    if job.is_settled() {
+        return Err(MatchMakerError::JobSettled(format!("Match job {} is already {}", job.id, job.status)));
+    }
+
+    let mut match_game = job.to_game();
+    result.apply_to(&mut match_game);
+
+    let output_dir = replay_directory(&match_game);
+    if let Err(e) = fs::create_dir_all(&output_dir) {
+        return Err(MatchMakerError::IOError(e));
+    }
+    let output_file = format!("{}/{}.zip", output_dir, match_game.id);
+    if let Err(e) = fs::copy(replay_file, &output_file) {
+        return Err(MatchMakerError::IOError(e));
+    }
+    match_game.log_file_path = output_file;
+    save_game_errors(&match_game, &errors)?;
+
+    if let Err(e) = store_game(match_game) {
+        // the same result submitted at the same time was stored first
+        if get_game_by_id(job.id.clone()).is_ok() {
+            return Ok(());
+        }
+        return Err(e);
+    }
+    if let Err(e) = set_match_job_status(job.id.clone(), "done".to_string(), "".to_string()) {
+        return Err(MatchMakerError::DatabaseError(e));
+    }
+    try_finalize_round(&job)
+}
+
+/// Marks a match job as failed after its worker could not play it, and finalizes the round
+/// if it was the last job of the round.
+pub fn fail_job(job_id: String, error: String) -> Result<(), MatchMakerError> {
+    let job = match get_match_job_by_id(job_id) {
+        Ok(j) => j,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+    if job.is_settled() {
+        return Ok(());
+    }
+
+    if let Err(e) = set_match_job_status(job.id.clone(), "failed".to_string(), error) {
+        return Err(MatchMakerError::DatabaseError(e));
+    }
+    try_finalize_round(&job)
+}
+
+fn try_finalize_round(job: &MatchJob) -> Result<(), MatchMakerError> {
+    let competition = match get_competition_by_id(job.competition_id.clone()) {
+        Ok(c) => c,
+        Err(e) => return Err(MatchMakerError::DatabaseError(e))
+    };
+    if competition.round != job.round {
+        return Ok(());
+    }
+    finalize_2v2_round(&competition)
+}
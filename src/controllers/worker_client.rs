@@ -0,0 +1,195 @@
+use std::{env, fs, path::Path, thread, time::Duration};
+
+use reqwest::{blocking::{Client, multipart::{Form, Part}}, StatusCode};
+
+use crate::models::{
+    errors::MatchMakerError,
+    worker::{NewWorker, PublicWorker, WorkerFailure, WorkerGameResult, WorkerMatchJob}
+};
+
+use super::{
+    file_handler::{save_to_zip, unzip_to_directory},
+    matchmaker_2v2::{parse_game_result, play_game, replay_directory}
+};
+
+/// Seconds to wait before asking the dashboard for work again when there was none.
+const POLL_INTERVAL_SECONDS: u64 = 5;
+
+/// Runs this process as a remote match worker.
+///
+/// The worker registers with the dashboard, then keeps claiming match jobs, playing them with
+/// the local `Evaluator.jar` and uploading the replay and the parsed result. It plays one game
+/// at a time and never returns.
+///
+/// The worker is configured with the following environment variables:
+/// - `DASHBOARD_URL` - The URL of the dashboard API, defaults to `http://localhost:$PORT/api`.
+/// - `WORKER_TOKEN` - The shared token the dashboard accepts from workers.
+/// - `WORKER_NAME` - The name the worker registers with, defaults to `$HOSTNAME`.
+///
+/// # Panics
+///
+/// Panics if `WORKER_TOKEN` is not set, or neither `DASHBOARD_URL` nor `PORT` is set.
+///
+pub fn run_worker() {
+    let base_url = env::var("DASHBOARD_URL").unwrap_or_else(|_| {
+        let port = env::var("PORT").expect("Either $DASHBOARD_URL or $PORT must be set");
+        format!("http://localhost:{}/api", port)
+    });
+    let token = env::var("WORKER_TOKEN").expect("$WORKER_TOKEN is not set");
+    let name = env::var("WORKER_NAME")
+        .or_else(|_| env::var("HOSTNAME"))
+        .unwrap_or("worker".to_string());
+    let connection = WorkerConnection { client: Client::new(), base_url, token };
+
+    let worker = loop {
+        match connection.register(name.clone()) {
+            Ok(w) => break w,
+            Err(e) => {
+                eprintln!("Error on registering worker: {}", e);
+                thread::sleep(Duration::from_secs(POLL_INTERVAL_SECONDS));
+            }
+        }
+    };
+    println!("Registered as worker {} ({})", worker.name, worker.id);
+
+    loop {
+        let job = match connection.claim(&worker.id) {
+            Ok(Some(job)) => job,
+            Ok(None) => {
+                thread::sleep(Duration::from_secs(POLL_INTERVAL_SECONDS));
+                continue;
+            },
+            Err(e) => {
+                eprintln!("Error on claiming a match job: {}", e);
+                thread::sleep(Duration::from_secs(POLL_INTERVAL_SECONDS));
+                continue;
+            }
+        };
+
+        println!("Playing match job {}", job.id);
+        if let Err(e) = play_job(&connection, &job) {
+            eprintln!("Error on playing match job {}: {}", job.id, e);
+            if let Err(e) = connection.fail(&job.id, e.to_string()) {
+                eprintln!("Error on reporting failed match job {}: {}", job.id, e);
+            }
+        }
+    }
+}
+
+/// Downloads the bots of a job, plays its game and uploads the result.
+fn play_job(connection: &WorkerConnection, job: &WorkerMatchJob) -> Result<(), MatchMakerError> {
+    let match_folder = Path::new("./resources/worker/matches").join(&job.id);
+    if let Err(e) = fs::create_dir_all(&match_folder) {
+        return Err(MatchMakerError::IOError(e));
+    }
+
+    let result = play_in_folder(connection, job, &match_folder);
+
+    if let Err(e) = fs::remove_dir_all(&match_folder) {
+        eprintln!("Failed removing match folder: {:?}", e);
+    }
+    result
+}
+
+fn play_in_folder(connection: &WorkerConnection, job: &WorkerMatchJob, match_folder: &Path) -> Result<(), MatchMakerError> {
+    let bots = [&job.team1bot1_id, &job.team1bot2_id, &job.team2bot1_id, &job.team2bot2_id];
+    for bot_id in bots {
+        let archive = connection.download_bot(bot_id)?;
+        unzip_to_directory(archive, &match_folder.join(bot_id))?;
+    }
+
+    let mut match_game = job.to_game();
+    let (output, errors) = play_game(match_folder, &mut match_game, &job.sandbox_limits, Duration::from_secs(job.match_timeout as u64))?;
+
+    // the replay is stored under the same path as on the dashboard, its name is kept inside the zip
+    let output_dir = replay_directory(&match_game);
+    if let Err(e) = fs::create_dir_all(&output_dir) {
+        return Err(MatchMakerError::IOError(e));
+    }
+    let output_file = format!("{}/{}.zip", output_dir, match_game.id);
+    save_to_zip(output.join("\n"), &output_file)?;
+    let replay = fs::read(&output_file).map_err(MatchMakerError::IOError)?;
+    if let Err(e) = fs::remove_file(&output_file) {
+        eprintln!("Failed removing local replay: {:?}", e);
+    }
+
+    let error_output = errors.join("\n");
+    parse_game_result(output, errors, &mut match_game);
+    connection.upload_result(&job.id, WorkerGameResult::from(&match_game), replay, error_output)
+}
+
+/// The dashboard a worker talks to.
+struct WorkerConnection {
+    client: Client,
+    base_url: String,
+    token: String,
+}
+
+impl WorkerConnection {
+    fn register(&self, name: String) -> Result<PublicWorker, MatchMakerError> {
+        self.client
+            .post(format!("{}/worker/register", self.base_url))
+            .bearer_auth(&self.token)
+            .json(&NewWorker { name })
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.json::<PublicWorker>())
+            .map_err(remote_error)
+    }
+
+    fn claim(&self, worker_id: &str) -> Result<Option<WorkerMatchJob>, MatchMakerError> {
+        let response = self.client
+            .post(format!("{}/worker/claim/{}", self.base_url, worker_id))
+            .bearer_auth(&self.token)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .map_err(remote_error)?;
+        if response.status() == StatusCode::NO_CONTENT {
+            return Ok(None);
+        }
+        response.json::<WorkerMatchJob>().map(Some).map_err(remote_error)
+    }
+
+    fn download_bot(&self, bot_id: &str) -> Result<Vec<u8>, MatchMakerError> {
+        self.client
+            .get(format!("{}/worker/bot/{}", self.base_url, bot_id))
+            .bearer_auth(&self.token)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.bytes())
+            .map(|bytes| bytes.to_vec())
+            .map_err(remote_error)
+    }
+
+    fn upload_result(&self, job_id: &str, result: WorkerGameResult, replay: Vec<u8>, errors: String) -> Result<(), MatchMakerError> {
+        let result = serde_json::to_string(&result)
+            .map_err(|e| MatchMakerError::RemoteWorkerError(e.to_string()))?;
+        let form = Form::new()
+            .text("result", result)
+            .text("errors", errors)
+            .part("replay", Part::bytes(replay).file_name(format!("{}.zip", job_id)));
+        self.client
+            .post(format!("{}/worker/result/{}", self.base_url, job_id))
+            .bearer_auth(&self.token)
+            .multipart(form)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .map(|_| ())
+            .map_err(remote_error)
+    }
+
+    fn fail(&self, job_id: &str, error: String) -> Result<(), MatchMakerError> {
+        self.client
+            .post(format!("{}/worker/failure/{}", self.base_url, job_id))
+            .bearer_auth(&self.token)
+            .json(&WorkerFailure { error })
+            .send()
+            .and_then(|response| response.error_for_status())
+            .map(|_| ())
+            .map_err(remote_error)
+    }
+}
+
+fn remote_error(error: reqwest::Error) -> MatchMakerError {
+    MatchMakerError::RemoteWorkerError(error.to_string())
+}
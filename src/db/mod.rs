@@ -4,4 +4,10 @@ pub mod operations_users;
 pub mod operations_teams;
 pub mod operations_competition;
 pub mod operations_bot;
-pub mod operations_game2v2;
\ No newline at end of file
+pub mod operations_game2v2;
+pub mod operations_leaderboard;
+pub mod operations_final_evaluation;
+pub mod operations_bracket;
+pub mod operations_match_job;
+pub mod operations_worker;
+pub mod operations_game_player_stats;
\ No newline at end of file
@@ -1,19 +1,12 @@
+use chrono::NaiveDateTime;
 use diesel::result::Error;
 use diesel::{prelude::*, insert_into};
 use crate::db::schema::bots::dsl::*;
+use crate::db::schema::teams;
 use crate::models::bot::{SqlBot, Bot, NewBot};
 use super::operations_db::establish_connection;
 
 
-pub fn insert_bot(bot: NewBot) ->  Result<Bot, Error> {
-    let new_bot = SqlBot::from(bot);
-    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
-    let _ = insert_into(bots)
-        .values(&new_bot)
-        .execute(&mut conn)?;
-    Ok(Bot::from(new_bot))
-}
-
 pub fn get_bot_by_id(uid: String) -> Result<Bot, Error> {
     let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
     match bots
@@ -45,14 +38,40 @@ pub fn get_bots_by_team(tid: String) -> Result<Vec<Bot>, Error> {
     }
 }
 
-pub fn set_bot_error(bot: Bot, error: String) -> Result<(), Error> {
+pub fn set_bot_compile_result(bid: String, hash: String, language: String, status: String, error: String, diagnostics: String) -> Result<(), Error> {
     let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
-    diesel::update(bots.filter(id.eq(bot.id)))
-        .set(compile_error.eq(error))
+    diesel::update(bots.filter(id.eq(bid)))
+        .set((source_hash.eq(hash), compile_language.eq(language), compile_status.eq(status), compile_error.eq(error), compile_diagnostics.eq(diagnostics)))
         .execute(&mut conn)?;
     Ok(())
 }
 
+/// Stores a bot unless its team has stored `quota` bots since `since` already, in which case
+/// `None` is returned. The team row is locked while counting, so concurrent uploads of a team
+/// can't both pass the quota.
+pub fn insert_bot_within_quota(bot: NewBot, since: NaiveDateTime, quota: i64) -> Result<Option<Bot>, Error> {
+    let new_bot = SqlBot::from(bot);
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    conn.transaction(|conn| {
+        let _ = teams::table
+            .filter(teams::id.eq(&new_bot.team_id))
+            .select(teams::id)
+            .for_update()
+            .first::<String>(conn)?;
+        let uploads: i64 = bots
+            .filter(team_id.eq(&new_bot.team_id).and(created.ge(since)))
+            .count()
+            .get_result(conn)?;
+        if uploads >= quota {
+            return Ok(None);
+        }
+        let _ = insert_into(bots)
+            .values(&new_bot)
+            .execute(conn)?;
+        Ok(Some(Bot::from(new_bot)))
+    })
+}
+
 
 pub fn get_bots_by_ids(ids: Vec<String>) -> Result<Vec<Bot>, Error> {
     let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
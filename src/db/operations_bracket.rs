@@ -0,0 +1,95 @@
+use diesel::result::Error;
+use diesel::{prelude::*, insert_into};
+use crate::db::schema::{brackets, bracket_matches};
+use crate::models::bracket::{
+    SqlBracket, Bracket, NewBracket,
+    SqlBracketMatch, BracketMatch, NewBracketMatch
+};
+use super::operations_db::establish_connection;
+
+
+pub fn insert_bracket(bracket: NewBracket) -> Result<Bracket, Error> {
+    let new_bracket = SqlBracket::from(bracket);
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let _ = insert_into(brackets::table)
+        .values(&new_bracket)
+        .execute(&mut conn)?;
+    Ok(Bracket::from(new_bracket))
+}
+
+pub fn get_latest_bracket(com_id: String) -> Result<Bracket, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    match brackets::table
+        .filter(brackets::competition_id.eq(com_id))
+        .order(brackets::created.desc())
+        .first::<SqlBracket>(&mut conn) {
+            Ok(b) => Ok(Bracket::from(b)),
+            Err(e) => Err(e)
+    }
+}
+
+pub fn get_brackets_by_status(bracket_status: String) -> Result<Vec<Bracket>, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let brackets = brackets::table
+        .filter(brackets::status.eq(bracket_status))
+        .load::<SqlBracket>(&mut conn)?;
+    Ok(brackets.into_iter().map(Bracket::from).collect::<Vec<Bracket>>())
+}
+
+pub fn set_bracket_status(bid: String, new_status: String) -> Result<(), Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    diesel::update(brackets::table.filter(brackets::id.eq(bid)))
+        .set(brackets::status.eq(new_status))
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+pub fn insert_bracket_matches(matches: Vec<NewBracketMatch>) -> Result<(), Error> {
+    let new_matches: Vec<SqlBracketMatch> = matches
+        .into_iter()
+        .map(SqlBracketMatch::from)
+        .collect();
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let _ = insert_into(bracket_matches::table)
+        .values(&new_matches)
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+pub fn get_bracket_matches(bid: String) -> Result<Vec<BracketMatch>, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let matches = bracket_matches::table
+        .filter(bracket_matches::bracket_id.eq(bid))
+        .order((
+            bracket_matches::stage.desc(),
+            bracket_matches::round.asc(),
+            bracket_matches::position.asc()
+        ))
+        .load::<SqlBracketMatch>(&mut conn)?;
+    Ok(matches.into_iter().map(BracketMatch::from).collect::<Vec<BracketMatch>>())
+}
+
+pub fn update_bracket_match(bracket_match: &BracketMatch) -> Result<(), Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    diesel::update(bracket_matches::table.filter(bracket_matches::id.eq(bracket_match.id.clone())))
+        .set((
+            bracket_matches::team1_wins.eq(bracket_match.team1_wins),
+            bracket_matches::team2_wins.eq(bracket_match.team2_wins),
+            bracket_matches::winner_id.eq(bracket_match.winner_id.clone()),
+            bracket_matches::loser_id.eq(bracket_match.loser_id.clone()),
+            bracket_matches::games.eq(bracket_match.games.clone()),
+            bracket_matches::status.eq(bracket_match.status.clone()),
+        ))
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+pub fn set_bracket_match_team(match_id: String, slot: i32, team_id: String) -> Result<(), Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let builder = diesel::update(bracket_matches::table.filter(bracket_matches::id.eq(match_id)));
+    match slot {
+        1 => builder.set(bracket_matches::team1_id.eq(team_id)).execute(&mut conn)?,
+        _ => builder.set(bracket_matches::team2_id.eq(team_id)).execute(&mut conn)?,
+    };
+    Ok(())
+}
@@ -57,4 +57,52 @@ pub fn set_competition_round(cid: String, new_round: i32) -> Result<(), Error> {
         .set(round.eq(new_round.to_string()))
         .execute(&mut conn)?;
     Ok(())
-}
\ No newline at end of file
+}
+
+pub fn set_competition_pairing(cid: String, strategy: String, params: String) -> Result<(), Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    diesel::update(competitions.filter(id.eq(cid)))
+        .set((pairing_strategy.eq(strategy), pairing_params.eq(params)))
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+pub fn set_competition_schedule(cid: String, new_schedule: String) -> Result<(), Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    diesel::update(competitions.filter(id.eq(cid)))
+        .set(schedule.eq(new_schedule))
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+pub fn set_competition_sandbox_limits(cid: String, limits: String) -> Result<(), Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    diesel::update(competitions.filter(id.eq(cid)))
+        .set(sandbox_limits.eq(limits))
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+pub fn set_competition_timeout(cid: String, new_timeout: i32, policy: String) -> Result<(), Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    diesel::update(competitions.filter(id.eq(cid)))
+        .set((match_timeout.eq(new_timeout), timeout_policy.eq(policy)))
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+pub fn set_competition_language(cid: String, new_language: String) -> Result<(), Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    diesel::update(competitions.filter(id.eq(cid)))
+        .set(language.eq(new_language))
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+pub fn set_competition_submissions(cid: String, allowed: bool, quota: i32, max_mb: i32) -> Result<(), Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    diesel::update(competitions.filter(id.eq(cid)))
+        .set((allowed_submissions.eq(allowed.to_string()), upload_quota.eq(quota), max_upload_mb.eq(max_mb)))
+        .execute(&mut conn)?;
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use diesel::result::Error;
+use diesel::{prelude::*, insert_into};
+use crate::db::schema::{final_evaluations, final_evaluation_standings};
+use crate::models::final_evaluation::{
+    SqlFinalEvaluation, FinalEvaluation, NewFinalEvaluation,
+    SqlFinalStanding, PublicFinalStanding, NewFinalStanding
+};
+use super::operations_db::establish_connection;
+
+
+pub fn insert_final_evaluation(evaluation: NewFinalEvaluation) -> Result<FinalEvaluation, Error> {
+    let new_evaluation = SqlFinalEvaluation::from(evaluation);
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let _ = insert_into(final_evaluations::table)
+        .values(&new_evaluation)
+        .execute(&mut conn)?;
+    Ok(FinalEvaluation::from(new_evaluation))
+}
+
+pub fn get_latest_final_evaluation(com_id: String) -> Result<FinalEvaluation, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    match final_evaluations::table
+        .filter(final_evaluations::competition_id.eq(com_id))
+        .order(final_evaluations::created.desc())
+        .first::<SqlFinalEvaluation>(&mut conn) {
+            Ok(e) => Ok(FinalEvaluation::from(e)),
+            Err(e) => Err(e)
+    }
+}
+
+pub fn get_final_evaluations_by_status(eval_status: String) -> Result<Vec<FinalEvaluation>, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let evaluations = final_evaluations::table
+        .filter(final_evaluations::status.eq(eval_status))
+        .load::<SqlFinalEvaluation>(&mut conn)?;
+    Ok(evaluations.into_iter().map(FinalEvaluation::from).collect::<Vec<FinalEvaluation>>())
+}
+
+pub fn set_final_evaluation_status(eval_id: String, new_status: String) -> Result<(), Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    diesel::update(final_evaluations::table.filter(final_evaluations::id.eq(eval_id)))
+        .set(final_evaluations::status.eq(new_status))
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+pub fn insert_final_standings(standings: Vec<NewFinalStanding>) -> Result<(), Error> {
+    let new_standings: Vec<SqlFinalStanding> = standings
+        .into_iter()
+        .map(SqlFinalStanding::from)
+        .collect();
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let _ = insert_into(final_evaluation_standings::table)
+        .values(&new_standings)
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+pub fn get_final_standings(eval_id: String) -> Result<Vec<PublicFinalStanding>, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let standings = final_evaluation_standings::table
+        .filter(final_evaluation_standings::evaluation_id.eq(eval_id))
+        .order(final_evaluation_standings::team_rank.asc())
+        .load::<SqlFinalStanding>(&mut conn)?;
+    Ok(standings.into_iter().map(PublicFinalStanding::from).collect::<Vec<PublicFinalStanding>>())
+}
@@ -1,17 +1,29 @@
 use diesel::result::Error;
 use diesel::{prelude::*, insert_into};
 use crate::db::schema::games_2v2::dsl::*;
+use crate::db::schema::game_player_stats;
 use crate::models::game_2v2::{SqlGame2v2, Game2v2, NewGame2v2};
+use crate::models::game_player_stats::SqlGamePlayerStats;
 use super::operations_db::establish_connection;
 
 
+/// Stores a game, together with the statistics of its players (see `SqlGamePlayerStats::from_game`).
 pub fn insert_game(game: NewGame2v2) ->  Result<Game2v2, Error> {
     let new_bot = SqlGame2v2::from(game);
     let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
-    let _ = insert_into(games_2v2)
-        .values(&new_bot)
-        .execute(&mut conn)?;
-    Ok(Game2v2::from(new_bot))
+    conn.transaction(|conn| {
+        let _ = insert_into(games_2v2)
+            .values(&new_bot)
+            .execute(conn)?;
+        let game = Game2v2::from(new_bot);
+        let stats = SqlGamePlayerStats::from_game(&game);
+        if !stats.is_empty() {
+            let _ = insert_into(game_player_stats::table)
+                .values(&stats)
+                .execute(conn)?;
+        }
+        Ok(game)
+    })
 }
 
 pub fn get_game_by_id(uid: String) -> Result<Game2v2, Error> {
@@ -33,6 +45,8 @@ pub fn get_games_by_bot_id(bot_id: String) -> Result<Vec<Game2v2>, Error> {
                 .or(team2bot1_id.eq(bot_id.clone()))
                 .or(team2bot2_id.eq(bot_id.clone()))    
         )
+        .filter(evaluation_id.eq(""))
+        .filter(bracket_id.eq(""))
         .distinct()
         .load::<SqlGame2v2>(&mut conn)?;
     Ok(games.into_iter().map(Game2v2::from).collect::<Vec<Game2v2>>())
@@ -48,6 +62,8 @@ pub fn get_rounds_for_competition(team_id: String, com_id: String) -> Result<Vec
         )
         .distinct()
         .filter(competition_id.eq(com_id))
+        .filter(evaluation_id.eq(""))
+        .filter(bracket_id.eq(""))
         .load::<SqlGame2v2>(&mut conn)?;
     Ok(games.into_iter().map(Game2v2::from).collect::<Vec<Game2v2>>())
 }
@@ -66,4 +82,40 @@ pub fn get_public_games() -> Result<Vec<Game2v2>, Error> {
         .filter(public.eq(true))
         .load::<SqlGame2v2>(&mut conn)?;
     Ok(games.into_iter().map(Game2v2::from).collect::<Vec<Game2v2>>())
-}
\ No newline at end of file
+}
+
+pub fn get_games_by_competition_id(com_id: String) -> Result<Vec<Game2v2>, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let games = games_2v2
+        .filter(competition_id.eq(com_id))
+        .filter(evaluation_id.eq(""))
+        .filter(bracket_id.eq(""))
+        .load::<SqlGame2v2>(&mut conn)?;
+    Ok(games.into_iter().map(Game2v2::from).collect::<Vec<Game2v2>>())
+}
+
+pub fn get_games_by_evaluation_id(eval_id: String) -> Result<Vec<Game2v2>, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let games = games_2v2
+        .filter(evaluation_id.eq(eval_id))
+        .load::<SqlGame2v2>(&mut conn)?;
+    Ok(games.into_iter().map(Game2v2::from).collect::<Vec<Game2v2>>())
+}
+pub fn get_games_by_ids(ids: Vec<String>) -> Result<Vec<Game2v2>, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let games = games_2v2
+        .filter(id.eq_any(ids))
+        .load::<SqlGame2v2>(&mut conn)?;
+    Ok(games.into_iter().map(Game2v2::from).collect::<Vec<Game2v2>>())
+}
+
+/// Returns a page of all games, oldest first.
+pub fn get_games_page(offset: i64, limit: i64) -> Result<Vec<Game2v2>, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let games = games_2v2
+        .order((created.asc(), id.asc()))
+        .offset(offset)
+        .limit(limit)
+        .load::<SqlGame2v2>(&mut conn)?;
+    Ok(games.into_iter().map(Game2v2::from).collect::<Vec<Game2v2>>())
+}
@@ -0,0 +1,32 @@
+use diesel::result::Error;
+use diesel::{prelude::*, replace_into};
+use crate::db::schema::game_player_stats::dsl::*;
+use crate::models::game_player_stats::SqlGamePlayerStats;
+use super::operations_db::establish_connection;
+
+
+/// Stores the statistics of players, replacing the ones stored for the same game and player.
+pub fn insert_player_stats(stats: Vec<SqlGamePlayerStats>) -> Result<(), Error> {
+    if stats.is_empty() {
+        return Ok(());
+    }
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let _ = replace_into(game_player_stats)
+        .values(&stats)
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+pub fn get_player_stats_by_bots(bot_ids: Vec<String>) -> Result<Vec<SqlGamePlayerStats>, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    game_player_stats
+        .filter(bot_id.eq_any(bot_ids))
+        .load::<SqlGamePlayerStats>(&mut conn)
+}
+
+pub fn get_player_stats_by_team(tid: String) -> Result<Vec<SqlGamePlayerStats>, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    game_player_stats
+        .filter(team_id.eq(tid))
+        .load::<SqlGamePlayerStats>(&mut conn)
+}
@@ -0,0 +1,36 @@
+use diesel::result::Error;
+use diesel::{prelude::*, insert_into};
+use crate::db::schema::leaderboard_snapshots::dsl::*;
+use crate::models::leaderboard::{SqlLeaderboardSnapshot, LeaderboardSnapshot, NewLeaderboardSnapshot};
+use super::operations_db::establish_connection;
+
+
+pub fn insert_snapshots(snapshots: Vec<NewLeaderboardSnapshot>) -> Result<(), Error> {
+    let new_snapshots: Vec<SqlLeaderboardSnapshot> = snapshots
+        .into_iter()
+        .map(SqlLeaderboardSnapshot::from)
+        .collect();
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let _ = insert_into(leaderboard_snapshots)
+        .values(&new_snapshots)
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+pub fn get_snapshots_by_team(tid: String) -> Result<Vec<LeaderboardSnapshot>, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let snapshots = leaderboard_snapshots
+        .filter(team_id.eq(tid))
+        .order(round.asc())
+        .load::<SqlLeaderboardSnapshot>(&mut conn)?;
+    Ok(snapshots.into_iter().map(LeaderboardSnapshot::from).collect::<Vec<LeaderboardSnapshot>>())
+}
+
+pub fn get_snapshots_by_round(com_id: String, snapshot_round: i32) -> Result<Vec<LeaderboardSnapshot>, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let snapshots = leaderboard_snapshots
+        .filter(competition_id.eq(com_id).and(round.eq(snapshot_round)))
+        .order(team_rank.asc())
+        .load::<SqlLeaderboardSnapshot>(&mut conn)?;
+    Ok(snapshots.into_iter().map(LeaderboardSnapshot::from).collect::<Vec<LeaderboardSnapshot>>())
+}
@@ -0,0 +1,93 @@
+use chrono::{Local, NaiveDateTime};
+use diesel::result::Error;
+use diesel::{prelude::*, insert_into};
+use crate::db::schema::match_jobs::dsl::*;
+use crate::models::match_job::{SqlMatchJob, MatchJob, NewMatchJob};
+use super::operations_db::establish_connection;
+
+
+pub fn insert_match_jobs(jobs: Vec<NewMatchJob>) -> Result<Vec<MatchJob>, Error> {
+    let new_jobs: Vec<SqlMatchJob> = jobs
+        .into_iter()
+        .map(SqlMatchJob::from)
+        .collect();
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let _ = insert_into(match_jobs)
+        .values(&new_jobs)
+        .execute(&mut conn)?;
+    Ok(new_jobs.into_iter().map(MatchJob::from).collect::<Vec<MatchJob>>())
+}
+
+pub fn get_match_jobs(com_id: String, com_round: i32) -> Result<Vec<MatchJob>, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let jobs = match_jobs
+        .filter(competition_id.eq(com_id).and(round.eq(com_round)))
+        .order(created.asc())
+        .load::<SqlMatchJob>(&mut conn)?;
+    Ok(jobs.into_iter().map(MatchJob::from).collect::<Vec<MatchJob>>())
+}
+
+pub fn get_unsettled_job_competition_ids() -> Result<Vec<String>, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    match_jobs
+        .filter(status.eq_any(vec!["pending", "running"]))
+        .select(competition_id)
+        .distinct()
+        .load::<String>(&mut conn)
+}
+
+pub fn set_match_job_status(job_id: String, new_status: String, new_error: String) -> Result<(), Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    diesel::update(match_jobs.filter(id.eq(job_id)))
+        .set((
+            status.eq(new_status),
+            error.eq(new_error),
+            updated.eq(Local::now().naive_utc()),
+        ))
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+pub fn get_match_job_by_id(job_id: String) -> Result<MatchJob, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    match match_jobs
+        .filter(id.eq(job_id))
+        .first::<SqlMatchJob>(&mut conn) {
+            Ok(j) => Ok(MatchJob::from(j)),
+            Err(e) => Err(e)
+    }
+}
+
+/// Claims the oldest pending job for a worker. Jobs that have been running since before
+/// `stale_before` are claimed as well, their worker is considered gone.
+pub fn claim_match_job(wid: String, stale_before: NaiveDateTime) -> Result<Option<MatchJob>, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let candidates = match_jobs
+        .filter(status.eq("pending").or(status.eq("running").and(updated.lt(stale_before))))
+        .order(created.asc())
+        .limit(10)
+        .load::<SqlMatchJob>(&mut conn)?;
+
+    let now = Local::now().naive_utc();
+    for candidate in candidates.into_iter() {
+        // only one worker can win the update, the others try the next candidate
+        let claimed = diesel::update(match_jobs
+            .filter(id.eq(candidate.id.clone()))
+            .filter(status.eq(candidate.status.clone()))
+            .filter(updated.eq(candidate.updated)))
+            .set((
+                status.eq("running"),
+                worker_id.eq(wid.clone()),
+                updated.eq(now),
+            ))
+            .execute(&mut conn)?;
+        if claimed == 1 {
+            let mut job = MatchJob::from(candidate);
+            job.status = "running".to_string();
+            job.worker_id = wid;
+            job.updated = now;
+            return Ok(Some(job));
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,42 @@
+use chrono::Local;
+use diesel::result::Error;
+use diesel::{prelude::*, insert_into};
+use crate::db::schema::workers::dsl::*;
+use crate::models::worker::{SqlWorker, Worker, NewWorker};
+use super::operations_db::establish_connection;
+
+
+pub fn insert_worker(worker: NewWorker) -> Result<Worker, Error> {
+    let new_worker = SqlWorker::from(worker);
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let _ = insert_into(workers)
+        .values(&new_worker)
+        .execute(&mut conn)?;
+    Ok(Worker::from(new_worker))
+}
+
+pub fn get_worker_by_id(wid: String) -> Result<Worker, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    match workers
+        .filter(id.eq(wid))
+        .first::<SqlWorker>(&mut conn) {
+            Ok(w) => Ok(Worker::from(w)),
+            Err(e) => Err(e)
+    }
+}
+
+pub fn get_workers() -> Result<Vec<Worker>, Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    let all_workers = workers
+        .order(last_seen.desc())
+        .load::<SqlWorker>(&mut conn)?;
+    Ok(all_workers.into_iter().map(Worker::from).collect::<Vec<Worker>>())
+}
+
+pub fn set_worker_seen(wid: String) -> Result<(), Error> {
+    let mut conn = establish_connection().expect("Failed to get a DB connection from the pool");
+    diesel::update(workers.filter(id.eq(wid)))
+        .set(last_seen.eq(Local::now().naive_utc()))
+        .execute(&mut conn)?;
+    Ok(())
+}
@@ -12,6 +12,62 @@ diesel::table! {
         source_path -> Varchar,
         compile_error -> Text,
         created -> Datetime,
+        #[max_length = 64]
+        source_hash -> Varchar,
+        #[max_length = 255]
+        compile_status -> Varchar,
+        compile_diagnostics -> Text,
+        #[max_length = 255]
+        compile_language -> Varchar,
+    }
+}
+
+diesel::table! {
+    bracket_matches (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        bracket_id -> Varchar,
+        #[max_length = 255]
+        stage -> Varchar,
+        round -> Integer,
+        position -> Integer,
+        #[max_length = 255]
+        team1_id -> Varchar,
+        #[max_length = 255]
+        team2_id -> Varchar,
+        team1_wins -> Integer,
+        team2_wins -> Integer,
+        #[max_length = 255]
+        winner_id -> Varchar,
+        #[max_length = 255]
+        loser_id -> Varchar,
+        #[max_length = 255]
+        winner_to -> Varchar,
+        winner_slot -> Integer,
+        #[max_length = 255]
+        loser_to -> Varchar,
+        loser_slot -> Integer,
+        games -> Text,
+        #[max_length = 255]
+        status -> Varchar,
+        created -> Datetime,
+    }
+}
+
+diesel::table! {
+    brackets (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        competition_id -> Varchar,
+        #[max_length = 255]
+        elimination -> Varchar,
+        size -> Integer,
+        best_of -> Integer,
+        #[max_length = 255]
+        status -> Varchar,
+        created -> Datetime,
     }
 }
 
@@ -34,6 +90,83 @@ diesel::table! {
         #[max_length = 255]
         game_pack -> Varchar,
         created -> Datetime,
+        #[max_length = 255]
+        pairing_strategy -> Varchar,
+        #[max_length = 1024]
+        pairing_params -> Varchar,
+        #[max_length = 255]
+        schedule -> Varchar,
+        #[max_length = 1024]
+        sandbox_limits -> Varchar,
+        match_timeout -> Integer,
+        #[max_length = 255]
+        timeout_policy -> Varchar,
+        #[max_length = 255]
+        language -> Varchar,
+        upload_quota -> Integer,
+        max_upload_mb -> Integer,
+    }
+}
+
+diesel::table! {
+    final_evaluation_standings (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        evaluation_id -> Varchar,
+        #[max_length = 255]
+        team_id -> Varchar,
+        team_rank -> Integer,
+        wins -> Integer,
+        losses -> Integer,
+        games_played -> Integer,
+        created -> Datetime,
+        draws -> Integer,
+    }
+}
+
+diesel::table! {
+    final_evaluations (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        competition_id -> Varchar,
+        games_per_pair -> Integer,
+        #[max_length = 255]
+        status -> Varchar,
+        created -> Datetime,
+    }
+}
+
+diesel::table! {
+    game_player_stats (game_id, player) {
+        #[max_length = 255]
+        game_id -> Varchar,
+        #[max_length = 255]
+        player -> Varchar,
+        #[max_length = 255]
+        bot_id -> Varchar,
+        #[max_length = 255]
+        team_id -> Varchar,
+        #[max_length = 255]
+        competition_id -> Varchar,
+        round -> Integer,
+        turns_played -> Integer,
+        survived -> Bool,
+        fleet_generated -> Integer,
+        fleet_lost -> Integer,
+        fleet_reinforced -> Integer,
+        largest_attack -> Integer,
+        largest_loss -> Integer,
+        largest_reinforcement -> Integer,
+        planets_lost -> Integer,
+        planets_conquered -> Integer,
+        planets_defended -> Integer,
+        planets_attacked -> Integer,
+        num_fleet_lost -> Integer,
+        num_fleet_reinforced -> Integer,
+        num_fleet_generated -> Integer,
+        total_troops_generated -> Integer,
     }
 }
 
@@ -70,6 +203,62 @@ diesel::table! {
         team1_elo -> Integer,
         team2_elo -> Integer,
         created -> Datetime,
+        #[max_length = 255]
+        evaluation_id -> Varchar,
+        leftover_processes -> Integer,
+        #[max_length = 255]
+        outcome -> Varchar,
+        draw -> Bool,
+        #[max_length = 255]
+        bracket_id -> Varchar,
+        void -> Bool,
+    }
+}
+
+diesel::table! {
+    leaderboard_snapshots (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        competition_id -> Varchar,
+        round -> Integer,
+        #[max_length = 255]
+        team_id -> Varchar,
+        team_rank -> Integer,
+        elo -> Integer,
+        wins -> Integer,
+        losses -> Integer,
+        games_played -> Integer,
+        created -> Datetime,
+    }
+}
+
+diesel::table! {
+    match_jobs (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        competition_id -> Varchar,
+        round -> Integer,
+        #[max_length = 255]
+        team1_id -> Varchar,
+        #[max_length = 255]
+        team2_id -> Varchar,
+        #[max_length = 255]
+        team1bot1_id -> Varchar,
+        #[max_length = 255]
+        team1bot2_id -> Varchar,
+        #[max_length = 255]
+        team2bot1_id -> Varchar,
+        #[max_length = 255]
+        team2bot2_id -> Varchar,
+        #[max_length = 255]
+        status -> Varchar,
+        error -> Text,
+        created -> Datetime,
+        updated -> Datetime,
+        #[max_length = 255]
+        worker_id -> Varchar,
     }
 }
 
@@ -108,10 +297,29 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    workers (id) {
+        #[max_length = 255]
+        id -> Varchar,
+        #[max_length = 255]
+        name -> Varchar,
+        last_seen -> Datetime,
+        created -> Datetime,
+    }
+}
+
 diesel::allow_tables_to_appear_in_same_query!(
     bots,
+    bracket_matches,
+    brackets,
     competitions,
+    final_evaluation_standings,
+    final_evaluations,
+    game_player_stats,
     games_2v2,
+    leaderboard_snapshots,
+    match_jobs,
     teams,
     users,
+    workers,
 );
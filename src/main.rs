@@ -3,7 +3,7 @@ use std::{env, thread};
 use actix_cors::Cors;
 use actix_web::HttpServer;
 use actix_web_httpauth::extractors::bearer::Config;
-use controllers::competitions::run_competitions_round;
+use controllers::{bracket::resume_brackets, competitions::{run_due_competitions, resume_unfinished_rounds}, final_evaluation::resume_final_evaluations, matchmaker_2v2::cleanup_matches, player_stats::backfill_player_stats, worker_client::run_worker};
 use dotenv::dotenv;
 use actix_web::{App, web, http, middleware::Logger};
 use tokio_cron_scheduler::{JobScheduler, Job};
@@ -25,18 +25,42 @@ use crate::routes::{
     user_id::user_id, 
     team_disband::team_disband, 
     team_bot_change::team_bot_change, 
-    matchmaking_test::mmt, 
     bot_win_rates::bots_win_rate, 
+    bot_stats::bots_stats,
     competition_rounds::competition_rounds, 
     game_log::game_log, 
+    game_log_zip::game_log_zip,
+    game_replay::game_replay,
     game_toggle_public::game_toggle_public, 
     competition_team_count::competition_team_count, 
     game_id::game_id, 
     competition_pack::competition_pack, 
+    competition_leaderboard::competition_leaderboard,
+    competition_leaderboard_round::competition_leaderboard_round,
+    competition_pairing::competition_pairing,
+    competition_schedule::competition_schedule,
+    competition_sandbox::competition_sandbox,
+    competition_timeout::competition_timeout,
+    competition_language::competition_language,
+    competition_submissions::competition_submissions,
+    competition_jobs::competition_jobs,
+    competition_round::competition_round,
+    competition_final::competition_final,
+    competition_final_start::competition_final_start,
+    competition_bracket::competition_bracket,
+    competition_bracket_start::competition_bracket_start,
     team_get_all::team_get_all, 
     game_get_public::game_get_public, 
     team_rename::team_name_change, 
     team_id::team_id,
+    team_history::team_history,
+    team_stats::team_stats,
+    worker_register::worker_register,
+    worker_claim::worker_claim,
+    worker_bot::worker_bot,
+    worker_result::worker_result,
+    worker_failure::worker_failure,
+    worker_get_all::worker_get_all,
 };
 
 mod routes;
@@ -46,10 +70,44 @@ mod models;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()>  {
+    // `worker` runs this binary as a remote match worker instead of the dashboard
+    if env::args().nth(1).as_deref() == Some("worker") {
+        dotenv().ok();
+        println!("[SETUP] Running as match worker.");
+        // the worker uses a blocking HTTP client, which can't run on the async runtime
+        if thread::spawn(run_worker).join().is_err() {
+            eprintln!("Worker stopped unexpectedly");
+        }
+        return Ok(());
+    }
+    // `backfill-stats` stores the player statistics of games played before they had a table
+    if env::args().nth(1).as_deref() == Some("backfill-stats") {
+        dotenv().ok();
+        match backfill_player_stats() {
+            Ok((games, stats)) => println!("Stored {} player statistics of {} games.", stats, games),
+            Err(e) => eprintln!("Error on backfilling player statistics: {:?}", e),
+        }
+        return Ok(());
+    }
+
     println!("[SETUP] Setting up environment.");
     let (port, url) = setup_env();
+
+    // no match is played yet, so whatever is left in the match directory was interrupted
+    if let Err(e) = cleanup_matches() {
+        println!("Error on cleaning up matches: {:?}", e)
+    }
    
     thread::spawn(|| {
+        if let Err(e) = resume_final_evaluations() {
+            println!("Error on resuming final evaluations: {:?}", e)
+        }
+        if let Err(e) = resume_brackets() {
+            println!("Error on resuming brackets: {:?}", e)
+        }
+        if let Err(e) = resume_unfinished_rounds() {
+            println!("Error on resuming rounds: {:?}", e)
+        }
         run_cron();
     });
 
@@ -77,6 +135,8 @@ async fn main() -> std::io::Result<()>  {
                 .service(user_me)
                 .service(user_id)
                 .service(login)
+                .service(team_history)
+                .service(team_stats)
                 .service(team_id)
                 .service(team_name_change)
                 .service(team_create)
@@ -90,18 +150,40 @@ async fn main() -> std::io::Result<()>  {
                 .service(team_get_all)
                 .service(bot_upload)
                 .service(bots_win_rate)
+                .service(bots_stats)
                 .service(competition_create)
+                .service(competition_pairing)
+                .service(competition_schedule)
+                .service(competition_sandbox)
+                .service(competition_timeout)
+                .service(competition_language)
+                .service(competition_submissions)
+                .service(competition_jobs)
+                .service(competition_round)
+                .service(competition_final)
+                .service(competition_final_start)
+                .service(competition_bracket)
+                .service(competition_bracket_start)
                 .service(competition_pack)
                 .service(competition_team_count)
                 .service(competition_running)
                 .service(competition_attended)
+                .service(competition_leaderboard)
+                .service(competition_leaderboard_round)
                 .service(competition_id)
                 .service(competition_rounds)
                 .service(game_log)
+                .service(game_log_zip)
+                .service(game_replay)
                 .service(game_toggle_public)
                 .service(game_get_public)
                 .service(game_id)
-                .service(mmt)
+                .service(worker_register)
+                .service(worker_claim)
+                .service(worker_bot)
+                .service(worker_result)
+                .service(worker_failure)
+                .service(worker_get_all)
             )
             
     });
@@ -118,11 +200,12 @@ fn setup_env() -> (u16, Option<String>) {
     (port, url)
 }
 
-/// Schedules and runs a cron job to execute the `run_competitions_round` function every hour.
+/// Schedules and runs a cron job to execute the `run_due_competitions` function every minute.
 ///
-/// This function sets up a cron job using the `JobScheduler` library. The cron job is scheduled to
-/// run at the start of every hour, every day, and it calls the `run_competitions_round` function.
-/// If there's any error while running the `run_competitions_round` function, the error is printed to the console.
+/// This function sets up a cron job using the `JobScheduler` library. The cron job ticks at the start of
+/// every minute and calls the `run_due_competitions` function, which plays a round for every competition
+/// whose own schedule is due. If there's any error while running the `run_due_competitions` function,
+/// the error is printed to the console.
 ///
 /// Additionally, a shutdown handler is set up for the scheduler. This handler prints a shutdown message
 /// when the scheduler is shutting down.
@@ -134,8 +217,8 @@ fn setup_env() -> (u16, Option<String>) {
 #[tokio::main]
 async fn run_cron() {
     let mut sched = JobScheduler::new();
-    match sched.add(Job::new_async("0 0 * * * * *", move |_, _|  Box::pin(async { 
-        if let Err(e) = run_competitions_round() {
+    match sched.add(Job::new_async("0 * * * * * *", move |_, _|  Box::pin(async { 
+        if let Err(e) = run_due_competitions() {
             println!("Error on running round: {:?}", e)
         }
     })).unwrap()) {
@@ -10,6 +10,16 @@ use crate::db::schema::bots::{self};
 pub struct NewBot {
     pub team_id: String,
     pub source_path: String,
+    pub source_hash: String,
+}
+
+/// An error reported by the compiler of a bot, stored as a JSON list on the bot.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CompileDiagnostic {
+    /// The file with the error, relative to the root of the bot.
+    pub file: String,
+    pub line: u32,
+    pub message: String,
 }
 
 #[derive(Debug, Clone)]
@@ -20,6 +30,13 @@ pub struct Bot {
     pub source_path: String,
     pub compile_error: String,
     pub created: NaiveDateTime,
+    pub source_hash: String,
+    /// `pending` until the bot is compiled, then `compiled` or `failed`.
+    pub compile_status: String,
+    /// JSON list of `CompileDiagnostic`s, empty unless the bot failed to compile.
+    pub compile_diagnostics: String,
+    /// The competition language the compile status is for, empty if it was detected.
+    pub compile_language: String,
 }   
 
 #[derive(Queryable, Debug, Insertable)]
@@ -31,6 +48,10 @@ pub struct SqlBot {
     pub source_path: String,
     pub compile_error: String,
     pub created: NaiveDateTime,
+    pub source_hash: String,
+    pub compile_status: String,
+    pub compile_diagnostics: String,
+    pub compile_language: String,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -40,6 +61,9 @@ pub struct PublicBot {
     pub bot_name: String,
     pub compile_error: String,
     pub created: NaiveDateTime,
+    pub source_hash: String,
+    pub compile_status: String,
+    pub compile_diagnostics: Vec<CompileDiagnostic>,
 }
 
 impl From<SqlBot> for Bot {
@@ -51,6 +75,10 @@ impl From<SqlBot> for Bot {
             source_path: sql_bot.source_path,
             compile_error: sql_bot.compile_error,
             created: sql_bot.created,
+            source_hash: sql_bot.source_hash,
+            compile_status: sql_bot.compile_status,
+            compile_diagnostics: sql_bot.compile_diagnostics,
+            compile_language: sql_bot.compile_language,
         }
     }
 }
@@ -63,6 +91,9 @@ impl From<Bot> for PublicBot {
             bot_name: bot.bot_name,
             compile_error: bot.compile_error,
             created: bot.created,
+            source_hash: bot.source_hash,
+            compile_status: bot.compile_status,
+            compile_diagnostics: serde_json::from_str(&bot.compile_diagnostics).unwrap_or_default(),
         }
     }
 }
@@ -82,6 +113,10 @@ impl From<NewBot> for SqlBot {
             source_path: new_bot.source_path,
             compile_error: "".to_string(),
             created: Local::now().naive_utc(),
+            source_hash: new_bot.source_hash,
+            compile_status: "pending".to_string(),
+            compile_diagnostics: "[]".to_string(),
+            compile_language: "".to_string(),
         }
     }
 }
\ No newline at end of file
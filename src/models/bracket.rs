@@ -0,0 +1,260 @@
+use diesel::prelude::{Insertable, Queryable};
+use serde::Serialize;
+use chrono::{NaiveDateTime, Local};
+use uuid::Uuid;
+use crate::db::schema::{brackets, bracket_matches};
+
+#[derive(Debug)]
+pub struct NewBracket {
+    pub competition_id: String,
+    pub elimination: String,
+    pub size: i32,
+    pub best_of: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Bracket {
+    pub id: String,
+    pub competition_id: String,
+    pub elimination: String,
+    pub size: i32,
+    pub best_of: i32,
+    pub status: String,
+    pub created: NaiveDateTime,
+}
+
+#[derive(Queryable, Debug, Insertable)]
+#[diesel(table_name = brackets)]
+pub struct SqlBracket {
+    pub id: String,
+    pub competition_id: String,
+    pub elimination: String,
+    pub size: i32,
+    pub best_of: i32,
+    pub status: String,
+    pub created: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PublicBracket {
+    pub id: String,
+    pub competition_id: String,
+    pub elimination: String,
+    pub size: i32,
+    pub best_of: i32,
+    pub status: String,
+    pub created: NaiveDateTime,
+    pub matches: Vec<PublicBracketMatch>,
+}
+
+#[derive(Debug)]
+pub struct NewBracketMatch {
+    pub id: String,
+    pub bracket_id: String,
+    pub stage: String,
+    pub round: i32,
+    pub position: i32,
+    pub team1_id: String,
+    pub team2_id: String,
+    pub winner_to: String,
+    pub winner_slot: i32,
+    pub loser_to: String,
+    pub loser_slot: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct BracketMatch {
+    pub id: String,
+    pub bracket_id: String,
+    pub stage: String,
+    pub round: i32,
+    pub position: i32,
+    pub team1_id: String,
+    pub team2_id: String,
+    pub team1_wins: i32,
+    pub team2_wins: i32,
+    pub winner_id: String,
+    pub loser_id: String,
+    pub winner_to: String,
+    pub winner_slot: i32,
+    pub loser_to: String,
+    pub loser_slot: i32,
+    pub games: String,
+    pub status: String,
+    pub created: NaiveDateTime,
+}
+
+#[derive(Queryable, Debug, Insertable)]
+#[diesel(table_name = bracket_matches)]
+pub struct SqlBracketMatch {
+    pub id: String,
+    pub bracket_id: String,
+    pub stage: String,
+    pub round: i32,
+    pub position: i32,
+    pub team1_id: String,
+    pub team2_id: String,
+    pub team1_wins: i32,
+    pub team2_wins: i32,
+    pub winner_id: String,
+    pub loser_id: String,
+    pub winner_to: String,
+    pub winner_slot: i32,
+    pub loser_to: String,
+    pub loser_slot: i32,
+    pub games: String,
+    pub status: String,
+    pub created: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PublicBracketMatch {
+    pub id: String,
+    pub bracket_id: String,
+    pub stage: String,
+    pub round: i32,
+    pub position: i32,
+    pub team1_id: String,
+    pub team2_id: String,
+    pub team1_wins: i32,
+    pub team2_wins: i32,
+    pub winner_id: String,
+    pub winner_to: String,
+    pub loser_to: String,
+    pub games: Vec<String>,
+    pub status: String,
+    pub created: NaiveDateTime,
+}
+
+impl From<SqlBracket> for Bracket {
+    fn from(sql_bracket: SqlBracket) -> Self {
+        Self {
+            id: sql_bracket.id,
+            competition_id: sql_bracket.competition_id,
+            elimination: sql_bracket.elimination,
+            size: sql_bracket.size,
+            best_of: sql_bracket.best_of,
+            status: sql_bracket.status,
+            created: sql_bracket.created,
+        }
+    }
+}
+
+impl From<NewBracket> for SqlBracket {
+    fn from(new_bracket: NewBracket) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            competition_id: new_bracket.competition_id,
+            elimination: new_bracket.elimination,
+            size: new_bracket.size,
+            best_of: new_bracket.best_of,
+            status: "running".to_string(),
+            created: Local::now().naive_utc(),
+        }
+    }
+}
+
+impl From<SqlBracketMatch> for BracketMatch {
+    fn from(sql_match: SqlBracketMatch) -> Self {
+        Self {
+            id: sql_match.id,
+            bracket_id: sql_match.bracket_id,
+            stage: sql_match.stage,
+            round: sql_match.round,
+            position: sql_match.position,
+            team1_id: sql_match.team1_id,
+            team2_id: sql_match.team2_id,
+            team1_wins: sql_match.team1_wins,
+            team2_wins: sql_match.team2_wins,
+            winner_id: sql_match.winner_id,
+            loser_id: sql_match.loser_id,
+            winner_to: sql_match.winner_to,
+            winner_slot: sql_match.winner_slot,
+            loser_to: sql_match.loser_to,
+            loser_slot: sql_match.loser_slot,
+            games: sql_match.games,
+            status: sql_match.status,
+            created: sql_match.created,
+        }
+    }
+}
+
+impl From<BracketMatch> for PublicBracketMatch {
+    fn from(bracket_match: BracketMatch) -> Self {
+        Self {
+            id: bracket_match.id,
+            bracket_id: bracket_match.bracket_id,
+            stage: bracket_match.stage,
+            round: bracket_match.round,
+            position: bracket_match.position,
+            team1_id: bracket_match.team1_id,
+            team2_id: bracket_match.team2_id,
+            team1_wins: bracket_match.team1_wins,
+            team2_wins: bracket_match.team2_wins,
+            winner_id: bracket_match.winner_id,
+            winner_to: bracket_match.winner_to,
+            loser_to: bracket_match.loser_to,
+            games: serde_json::from_str(&bracket_match.games).unwrap_or_default(),
+            status: bracket_match.status,
+            created: bracket_match.created,
+        }
+    }
+}
+
+impl From<NewBracketMatch> for SqlBracketMatch {
+    fn from(new_match: NewBracketMatch) -> Self {
+        Self {
+            id: new_match.id,
+            bracket_id: new_match.bracket_id,
+            stage: new_match.stage,
+            round: new_match.round,
+            position: new_match.position,
+            team1_id: new_match.team1_id,
+            team2_id: new_match.team2_id,
+            team1_wins: 0,
+            team2_wins: 0,
+            winner_id: "".to_string(),
+            loser_id: "".to_string(),
+            winner_to: new_match.winner_to,
+            winner_slot: new_match.winner_slot,
+            loser_to: new_match.loser_to,
+            loser_slot: new_match.loser_slot,
+            games: "[]".to_string(),
+            status: "pending".to_string(),
+            created: Local::now().naive_utc(),
+        }
+    }
+}
+
+impl NewBracketMatch {
+    pub fn new(bracket_id: String, stage: &str, round: i32, position: i32) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            bracket_id,
+            stage: stage.to_string(),
+            round,
+            position,
+            team1_id: "".to_string(),
+            team2_id: "".to_string(),
+            winner_to: "".to_string(),
+            winner_slot: 0,
+            loser_to: "".to_string(),
+            loser_slot: 0,
+        }
+    }
+}
+
+impl PublicBracket {
+    pub fn new(bracket: Bracket, matches: Vec<PublicBracketMatch>) -> Self {
+        Self {
+            id: bracket.id,
+            competition_id: bracket.competition_id,
+            elimination: bracket.elimination,
+            size: bracket.size,
+            best_of: bracket.best_of,
+            status: bracket.status,
+            created: bracket.created,
+            matches,
+        }
+    }
+}
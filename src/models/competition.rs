@@ -4,12 +4,96 @@ use chrono::{NaiveDateTime, Local};
 use uuid::Uuid;
 use crate::db::schema::competitions::{self};
 
+/// Schedule of new competitions: a round at the start of every hour.
+pub const DEFAULT_SCHEDULE: &str = "0 0 * * * * *";
+
+/// Seconds a game of a new competition may take before it is killed.
+pub const DEFAULT_MATCH_TIMEOUT: i32 = 120;
+
+/// Longest allowed match timeout in seconds. Jobs claimed by remote workers are handed out
+/// again after ten minutes, games have to be over by then.
+pub const MAX_MATCH_TIMEOUT: i32 = 480;
+
+/// How games that did not finish are rated: `score` rates them by the score they ended with,
+/// `draw` as a draw and `void` not at all.
+pub const TIMEOUT_POLICIES: [&str; 3] = ["score", "draw", "void"];
+
+/// Bots a team of a new competition may upload within 24 hours.
+pub const DEFAULT_UPLOAD_QUOTA: i32 = 20;
+
+/// Size in MB an uploaded bot of a new competition may have.
+pub const DEFAULT_MAX_UPLOAD_MB: i32 = 10;
+
+/// Parameters of a competition's pairing strategy, stored as JSON on the competition.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct PairingParams {
+    /// Maximal ELO difference of paired teams for the `elo_window` strategy.
+    pub window: Option<i32>,
+}
+
+/// Resource limits of the processes of a game, stored as JSON on the competition.
+///
+/// Missing fields fall back to their defaults.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct SandboxLimits {
+    /// Memory of the whole game in MiB. Enforced per process if no cgroup is available.
+    pub memory_mb: u64,
+    /// CPU time of every process in seconds.
+    pub cpu_seconds: u64,
+    /// Processes and threads of the whole game, only enforced with a cgroup.
+    pub max_processes: u64,
+    /// Open files of every process.
+    pub max_open_files: u64,
+    /// Size of every file written in MiB.
+    pub max_file_size_mb: u64,
+}
+
+impl Default for SandboxLimits {
+    fn default() -> Self {
+        Self {
+            memory_mb: 4096,
+            cpu_seconds: 300,
+            max_processes: 512,
+            max_open_files: 1024,
+            max_file_size_mb: 64,
+        }
+    }
+}
+
+impl PairingParams {
+    /// Returns whether the ELO window (if set) is not negative.
+    pub fn is_valid(&self) -> bool {
+        self.window.unwrap_or(0) >= 0
+    }
+}
+
+impl SandboxLimits {
+    /// Returns whether every limit is set to a positive value.
+    pub fn is_valid(&self) -> bool {
+        self.memory_mb > 0
+            && self.cpu_seconds > 0
+            && self.max_processes > 0
+            && self.max_open_files > 0
+            && self.max_file_size_mb > 0
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct NewCompetition {
     name: String,
     start: NaiveDateTime,
     end: NaiveDateTime,
     type_: String,
+    pub pairing_strategy: Option<String>,
+    pub pairing_params: Option<PairingParams>,
+    pub schedule: Option<String>,
+    pub sandbox_limits: Option<SandboxLimits>,
+    pub match_timeout: Option<i32>,
+    pub timeout_policy: Option<String>,
+    pub language: Option<String>,
+    pub upload_quota: Option<i32>,
+    pub max_upload_mb: Option<i32>,
 }
 
 #[derive(Debug)]
@@ -24,6 +108,17 @@ pub struct Competition {
     pub games_per_round: i32,
     pub game_pack: String,
     pub created: NaiveDateTime,
+    pub pairing_strategy: String,
+    pub pairing_params: String,
+    pub schedule: String,
+    pub sandbox_limits: String,
+    pub match_timeout: i32,
+    pub timeout_policy: String,
+    /// Language of the competition's bots, see `language_runtime`. Empty to detect it per bot.
+    pub language: String,
+    /// Bots a team may upload within 24 hours.
+    pub upload_quota: i32,
+    pub max_upload_mb: i32,
 }   
 
 #[derive(Queryable, Debug, Insertable)]
@@ -39,6 +134,15 @@ pub struct SqlCompetition {
     pub games_per_round: i32,
     pub game_pack: String,
     pub created: NaiveDateTime,
+    pub pairing_strategy: String,
+    pub pairing_params: String,
+    pub schedule: String,
+    pub sandbox_limits: String,
+    pub match_timeout: i32,
+    pub timeout_policy: String,
+    pub language: String,
+    pub upload_quota: i32,
+    pub max_upload_mb: i32,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -51,6 +155,15 @@ pub struct PublicCompetition {
     pub round: i32,
     pub type_: String,
     created: NaiveDateTime,
+    pub pairing_strategy: String,
+    pub pairing_params: String,
+    pub schedule: String,
+    pub sandbox_limits: String,
+    pub match_timeout: i32,
+    pub timeout_policy: String,
+    pub language: String,
+    pub upload_quota: i32,
+    pub max_upload_mb: i32,
 }
 
 impl From<SqlCompetition> for Competition {
@@ -66,6 +179,15 @@ impl From<SqlCompetition> for Competition {
             games_per_round: sql_competition.games_per_round,
             game_pack: sql_competition.game_pack,
             created: sql_competition.created,
+            pairing_strategy: sql_competition.pairing_strategy,
+            pairing_params: sql_competition.pairing_params,
+            schedule: sql_competition.schedule,
+            sandbox_limits: sql_competition.sandbox_limits,
+            match_timeout: sql_competition.match_timeout,
+            timeout_policy: sql_competition.timeout_policy,
+            language: sql_competition.language,
+            upload_quota: sql_competition.upload_quota,
+            max_upload_mb: sql_competition.max_upload_mb,
         }
     }
 }
@@ -81,6 +203,15 @@ impl From<Competition> for PublicCompetition {
             round: competition.round,
             type_: competition.type_,
             created: competition.created,
+            pairing_strategy: competition.pairing_strategy,
+            pairing_params: competition.pairing_params,
+            schedule: competition.schedule,
+            sandbox_limits: competition.sandbox_limits,
+            match_timeout: competition.match_timeout,
+            timeout_policy: competition.timeout_policy,
+            language: competition.language,
+            upload_quota: competition.upload_quota,
+            max_upload_mb: competition.max_upload_mb,
         }
     }
 }
@@ -98,6 +229,17 @@ impl From<NewCompetition> for SqlCompetition {
             games_per_round: 6,
             game_pack: format!("./resources/packs/Batalja{}Pack.zip", new_competition.type_),
             created: Local::now().naive_utc(),
+            pairing_strategy: new_competition.pairing_strategy.unwrap_or("random".to_string()),
+            pairing_params: serde_json::to_string(&new_competition.pairing_params.unwrap_or_default())
+                .unwrap_or("{}".to_string()),
+            schedule: new_competition.schedule.unwrap_or(DEFAULT_SCHEDULE.to_string()),
+            sandbox_limits: serde_json::to_string(&new_competition.sandbox_limits.unwrap_or_default())
+                .unwrap_or("{}".to_string()),
+            match_timeout: new_competition.match_timeout.unwrap_or(DEFAULT_MATCH_TIMEOUT),
+            timeout_policy: new_competition.timeout_policy.unwrap_or("score".to_string()),
+            language: new_competition.language.unwrap_or_default(),
+            upload_quota: new_competition.upload_quota.unwrap_or(DEFAULT_UPLOAD_QUOTA),
+            max_upload_mb: new_competition.max_upload_mb.unwrap_or(DEFAULT_MAX_UPLOAD_MB),
         }
     }
 }
\ No newline at end of file
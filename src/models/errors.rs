@@ -13,6 +13,14 @@ pub enum MatchMakerError {
     ZippingError(ZipError),
     PlayerFileMissing,
     MainMethodNotInPlayerFile,
+    NotEnoughTeams,
+    RemoteWorkerError(String),
+    SandboxError(String),
+    CompileError(String),
+    UnsafeZip(String),
+    AlreadyRunning(String),
+    UndecidedMatch(String),
+    JobSettled(String),
 }
 
 // Implement std::fmt::Display for MatchMakerError
@@ -27,6 +35,14 @@ impl fmt::Display for MatchMakerError {
             MatchMakerError::ZippingError(err) => writeln!(f, "ZippingError: {}", err),
             MatchMakerError::PlayerFileMissing => writeln!(f, "PlayerFileMissing Error"),
             MatchMakerError::MainMethodNotInPlayerFile => writeln!(f, "MainMethodNotInPlayerFile Error"),
+            MatchMakerError::NotEnoughTeams => writeln!(f, "NotEnoughTeams Error"),
+            MatchMakerError::RemoteWorkerError(err) => writeln!(f, "RemoteWorkerError: {}", err),
+            MatchMakerError::SandboxError(err) => writeln!(f, "SandboxError: {}", err),
+            MatchMakerError::CompileError(err) => writeln!(f, "CompileError: {}", err),
+            MatchMakerError::UnsafeZip(err) => writeln!(f, "UnsafeZip: {}", err),
+            MatchMakerError::AlreadyRunning(err) => writeln!(f, "AlreadyRunning: {}", err),
+            MatchMakerError::UndecidedMatch(err) => writeln!(f, "UndecidedMatch: {}", err),
+            MatchMakerError::JobSettled(err) => writeln!(f, "JobSettled: {}", err),
         }
     }
 }
@@ -43,6 +59,14 @@ impl fmt::Debug for MatchMakerError {
             MatchMakerError::ZippingError(err) => writeln!(f, "MatchMakerError::ZippingError: {:?}", err),
             MatchMakerError::PlayerFileMissing => writeln!(f, "MatchMakerError::PlayerFileMissing"),
             MatchMakerError::MainMethodNotInPlayerFile => writeln!(f, "MatchMakerError::MainMethodNotInPlayerFile"),
+            MatchMakerError::NotEnoughTeams => writeln!(f, "MatchMakerError::NotEnoughTeams"),
+            MatchMakerError::RemoteWorkerError(err) => writeln!(f, "MatchMakerError::RemoteWorkerError: {:?}", err),
+            MatchMakerError::SandboxError(err) => writeln!(f, "MatchMakerError::SandboxError: {:?}", err),
+            MatchMakerError::CompileError(err) => writeln!(f, "MatchMakerError::CompileError: {:?}", err),
+            MatchMakerError::UnsafeZip(err) => writeln!(f, "MatchMakerError::UnsafeZip: {:?}", err),
+            MatchMakerError::AlreadyRunning(err) => writeln!(f, "MatchMakerError::AlreadyRunning: {:?}", err),
+            MatchMakerError::UndecidedMatch(err) => writeln!(f, "MatchMakerError::UndecidedMatch: {:?}", err),
+            MatchMakerError::JobSettled(err) => writeln!(f, "MatchMakerError::JobSettled: {:?}", err),
         }
     }
 }
@@ -59,6 +83,14 @@ impl Error for MatchMakerError {
             MatchMakerError::ZippingError(err) => Some(err),
             MatchMakerError::PlayerFileMissing => None,
             MatchMakerError::MainMethodNotInPlayerFile => None,
+            MatchMakerError::NotEnoughTeams => None,
+            MatchMakerError::RemoteWorkerError(_) => None,
+            MatchMakerError::SandboxError(_) => None,
+            MatchMakerError::CompileError(_) => None,
+            MatchMakerError::UnsafeZip(_) => None,
+            MatchMakerError::AlreadyRunning(_) => None,
+            MatchMakerError::UndecidedMatch(_) => None,
+            MatchMakerError::JobSettled(_) => None,
         }
     }
 }
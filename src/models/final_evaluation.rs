@@ -0,0 +1,141 @@
+use diesel::prelude::{Insertable, Queryable};
+use serde::Serialize;
+use chrono::{NaiveDateTime, Local};
+use uuid::Uuid;
+use crate::db::schema::{final_evaluations, final_evaluation_standings};
+
+#[derive(Debug)]
+pub struct NewFinalEvaluation {
+    pub competition_id: String,
+    pub games_per_pair: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct FinalEvaluation {
+    pub id: String,
+    pub competition_id: String,
+    pub games_per_pair: i32,
+    pub status: String,
+    pub created: NaiveDateTime,
+}
+
+#[derive(Queryable, Debug, Insertable)]
+#[diesel(table_name = final_evaluations)]
+pub struct SqlFinalEvaluation {
+    pub id: String,
+    pub competition_id: String,
+    pub games_per_pair: i32,
+    pub status: String,
+    pub created: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PublicFinalEvaluation {
+    pub id: String,
+    pub competition_id: String,
+    pub games_per_pair: i32,
+    pub status: String,
+    pub created: NaiveDateTime,
+    pub standings: Vec<PublicFinalStanding>,
+}
+
+#[derive(Debug)]
+pub struct NewFinalStanding {
+    pub evaluation_id: String,
+    pub team_id: String,
+    pub team_rank: i32,
+    pub wins: i32,
+    pub losses: i32,
+    pub draws: i32,
+    pub games_played: i32,
+}
+
+#[derive(Queryable, Debug, Insertable)]
+#[diesel(table_name = final_evaluation_standings)]
+pub struct SqlFinalStanding {
+    pub id: String,
+    pub evaluation_id: String,
+    pub team_id: String,
+    pub team_rank: i32,
+    pub wins: i32,
+    pub losses: i32,
+    pub games_played: i32,
+    pub created: NaiveDateTime,
+    pub draws: i32,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PublicFinalStanding {
+    pub team_id: String,
+    pub rank: i32,
+    pub wins: i32,
+    pub losses: i32,
+    pub draws: i32,
+    pub games_played: i32,
+}
+
+impl From<SqlFinalEvaluation> for FinalEvaluation {
+    fn from(sql_evaluation: SqlFinalEvaluation) -> Self {
+        Self {
+            id: sql_evaluation.id,
+            competition_id: sql_evaluation.competition_id,
+            games_per_pair: sql_evaluation.games_per_pair,
+            status: sql_evaluation.status,
+            created: sql_evaluation.created,
+        }
+    }
+}
+
+impl From<NewFinalEvaluation> for SqlFinalEvaluation {
+    fn from(new_evaluation: NewFinalEvaluation) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            competition_id: new_evaluation.competition_id,
+            games_per_pair: new_evaluation.games_per_pair,
+            status: "running".to_string(),
+            created: Local::now().naive_utc(),
+        }
+    }
+}
+
+impl From<SqlFinalStanding> for PublicFinalStanding {
+    fn from(sql_standing: SqlFinalStanding) -> Self {
+        Self {
+            team_id: sql_standing.team_id,
+            rank: sql_standing.team_rank,
+            wins: sql_standing.wins,
+            losses: sql_standing.losses,
+            draws: sql_standing.draws,
+            games_played: sql_standing.games_played,
+        }
+    }
+}
+
+impl From<NewFinalStanding> for SqlFinalStanding {
+    fn from(new_standing: NewFinalStanding) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            evaluation_id: new_standing.evaluation_id,
+            team_id: new_standing.team_id,
+            team_rank: new_standing.team_rank,
+            wins: new_standing.wins,
+            losses: new_standing.losses,
+            games_played: new_standing.games_played,
+            created: Local::now().naive_utc(),
+            draws: new_standing.draws,
+        }
+    }
+}
+
+impl PublicFinalEvaluation {
+    pub fn new(evaluation: FinalEvaluation, standings: Vec<PublicFinalStanding>) -> Self {
+        Self {
+            id: evaluation.id,
+            competition_id: evaluation.competition_id,
+            games_per_pair: evaluation.games_per_pair,
+            status: evaluation.status,
+            created: evaluation.created,
+            standings,
+        }
+    }
+}
@@ -3,6 +3,7 @@ use serde::{Serialize, Deserialize};
 use chrono::{NaiveDateTime, Local};
 use uuid::Uuid;
 use crate::db::schema::games_2v2::{self};
+use super::team::Team;
 
 #[derive(Debug, Deserialize)]
 pub struct NewGame2v2 {
@@ -25,6 +26,20 @@ pub struct NewGame2v2 {
     pub team1_elo: i32,
     pub team2_elo: i32,
     pub additional_data: String,
+    pub evaluation_id: String,
+    /// The number of bot processes still running when the game ended (finished or timed out),
+    /// they were killed along with the game.
+    pub leftover_processes: i32,
+    /// `finished`, `timeout` if the game was killed after the match timeout, or `aborted` if
+    /// the evaluator failed.
+    pub outcome: String,
+    /// Whether neither team won. Drawn games have no winner and are rated 0.5 for both teams.
+    pub draw: bool,
+    /// The bracket the game was played in, empty for ladder and final evaluation games.
+    pub bracket_id: String,
+    /// Whether the game did not count, because it did not finish and the competition's timeout
+    /// policy is `void`. Void games have no winner, are not a draw and leave the ELO unchanged.
+    pub void: bool,
 }
 
 #[derive(Debug)]
@@ -49,6 +64,12 @@ pub struct Game2v2 {
     pub team1_elo: i32,
     pub team2_elo: i32,
     pub created: NaiveDateTime,
+    pub evaluation_id: String,
+    pub leftover_processes: i32,
+    pub outcome: String,
+    pub draw: bool,
+    pub bracket_id: String,
+    pub void: bool,
 }   
 
 #[derive(Queryable, Debug, Insertable)]
@@ -74,6 +95,12 @@ pub struct SqlGame2v2 {
     pub team1_elo: i32,
     pub team2_elo: i32,
     pub created: NaiveDateTime,
+    pub evaluation_id: String,
+    pub leftover_processes: i32,
+    pub outcome: String,
+    pub draw: bool,
+    pub bracket_id: String,
+    pub void: bool,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -97,6 +124,12 @@ pub struct PublicGame2v2 {
     pub team1_elo: i32,
     pub team2_elo: i32,
     pub created: NaiveDateTime,
+    pub evaluation_id: String,
+    pub leftover_processes: i32,
+    pub outcome: String,
+    pub draw: bool,
+    pub bracket_id: String,
+    pub void: bool,
 }
 
 impl From<SqlGame2v2> for Game2v2 {
@@ -122,6 +155,12 @@ impl From<SqlGame2v2> for Game2v2 {
             team1_elo: sql_game_2v2.team1_elo,
             team2_elo: sql_game_2v2.team2_elo,
             created: sql_game_2v2.created,
+            evaluation_id: sql_game_2v2.evaluation_id,
+            leftover_processes: sql_game_2v2.leftover_processes,
+            outcome: sql_game_2v2.outcome,
+            draw: sql_game_2v2.draw,
+            bracket_id: sql_game_2v2.bracket_id,
+            void: sql_game_2v2.void,
         }
     }
 }
@@ -148,6 +187,12 @@ impl From<Game2v2> for PublicGame2v2 {
             team1_elo: game_2v2.team1_elo,
             team2_elo: game_2v2.team2_elo,
             created: game_2v2.created,
+            evaluation_id: game_2v2.evaluation_id,
+            leftover_processes: game_2v2.leftover_processes,
+            outcome: game_2v2.outcome,
+            draw: game_2v2.draw,
+            bracket_id: game_2v2.bracket_id,
+            void: game_2v2.void,
         }
     }
 }
@@ -175,22 +220,23 @@ impl From<NewGame2v2> for SqlGame2v2 {
             team1_elo: new_game_2v2.team1_elo,
             team2_elo: new_game_2v2.team2_elo,
             created: Local::now().naive_utc(),
+            evaluation_id: new_game_2v2.evaluation_id,
+            leftover_processes: new_game_2v2.leftover_processes,
+            outcome: new_game_2v2.outcome,
+            draw: new_game_2v2.draw,
+            bracket_id: new_game_2v2.bracket_id,
+            void: new_game_2v2.void,
         }
     }
 }
 
 impl NewGame2v2 {
-    pub fn new(
-        competition_id: String, 
-        round: i32,
-        team1_id: String,
-        team2_id: String,
-        team1bot1_id: String,
-        team1bot2_id: String,
-        team2bot1_id: String,
-        team2bot2_id: String,
-    ) -> Self {
+    /// Creates a game between two teams, `bots` holds the bots in their seats (both bots of
+    /// team 1, then both bots of team 2).
+    pub fn new(competition_id: String, round: i32, teams: [String; 2], bots: [String; 4]) -> Self {
         let id = Uuid::new_v4().to_string();
+        let [team1_id, team2_id] = teams;
+        let [team1bot1_id, team1bot2_id, team2bot1_id, team2bot2_id] = bots;
         Self {
             id,
             competition_id,
@@ -211,6 +257,21 @@ impl NewGame2v2 {
             team1_elo: 0,
             team2_elo: 0,
             additional_data: "".to_string(),
+            evaluation_id: "".to_string(),
+            leftover_processes: 0,
+            outcome: "finished".to_string(),
+            draw: false,
+            bracket_id: "".to_string(),
+            void: false,
         }
     }
+
+    pub fn from_teams(competition_id: String, round: i32, team1: &Team, team2: &Team) -> Self {
+        Self::new(
+            competition_id,
+            round,
+            [team1.id.clone(), team2.id.clone()],
+            [team1.bot1.clone(), team1.bot2.clone(), team2.bot1.clone(), team2.bot2.clone()],
+        )
+    }
 }
\ No newline at end of file
@@ -1,6 +1,11 @@
+use std::collections::{HashMap, HashSet};
+
+use diesel::prelude::{Insertable, Queryable};
 use serde::{Serialize, Deserialize};
+use crate::db::schema::game_player_stats::{self};
+use super::game_2v2::Game2v2;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct GamePlayerStats {
     pub turns_played: i32,
     pub survived: bool,
@@ -37,4 +42,133 @@ impl Default for GameError {
     fn default() -> Self {
         Self { error: Default::default(), blame_id: Default::default() }
     }
-}
\ No newline at end of file
+}
+
+/// The statistics of a player of a game, one row per player and game.
+#[derive(Queryable, Debug, Insertable)]
+#[diesel(table_name = game_player_stats)]
+pub struct SqlGamePlayerStats {
+    pub game_id: String,
+    /// The player's key in the game, e.g. `team1bot2`.
+    pub player: String,
+    pub bot_id: String,
+    pub team_id: String,
+    pub competition_id: String,
+    pub round: i32,
+    pub turns_played: i32,
+    pub survived: bool,
+    pub fleet_generated: i32,
+    pub fleet_lost: i32,
+    pub fleet_reinforced: i32,
+    pub largest_attack: i32,
+    pub largest_loss: i32,
+    pub largest_reinforcement: i32,
+    pub planets_lost: i32,
+    pub planets_conquered: i32,
+    pub planets_defended: i32,
+    pub planets_attacked: i32,
+    pub num_fleet_lost: i32,
+    pub num_fleet_reinforced: i32,
+    pub num_fleet_generated: i32,
+    pub total_troops_generated: i32,
+}
+
+/// Averages of the statistics of a bot or team over the games it played. The averages of a team
+/// are over the games of both its bots.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct PublicStatsSummary {
+    pub games_played: i32,
+    pub survival_rate: f32,
+    pub turns_played: f32,
+    pub fleet_generated: f32,
+    pub fleet_lost: f32,
+    pub fleet_reinforced: f32,
+    pub largest_attack: f32,
+    pub largest_loss: f32,
+    pub largest_reinforcement: f32,
+    pub planets_lost: f32,
+    pub planets_conquered: f32,
+    pub planets_defended: f32,
+    pub planets_attacked: f32,
+    pub num_fleet_lost: f32,
+    pub num_fleet_reinforced: f32,
+    pub num_fleet_generated: f32,
+    pub total_troops_generated: f32,
+}
+
+impl SqlGamePlayerStats {
+    /// Returns the statistics of the players of a game, read from its `additional_data`.
+    /// Bugged games hold an error instead and have none.
+    pub fn from_game(game: &Game2v2) -> Vec<Self> {
+        let mut stats: HashMap<String, GamePlayerStats> = match serde_json::from_str(&game.additional_data) {
+            Ok(stats) => stats,
+            Err(_) => return Vec::new(),
+        };
+        let players = [
+            ("team1bot1", &game.team1bot1_id, &game.team1_id),
+            ("team1bot2", &game.team1bot2_id, &game.team1_id),
+            ("team2bot1", &game.team2bot1_id, &game.team2_id),
+            ("team2bot2", &game.team2bot2_id, &game.team2_id),
+        ];
+
+        players
+            .into_iter()
+            .filter_map(|(player, bot_id, team_id)| {
+                stats.remove(player).map(|s| Self {
+                    game_id: game.id.clone(),
+                    player: player.to_string(),
+                    bot_id: bot_id.clone(),
+                    team_id: team_id.clone(),
+                    competition_id: game.competition_id.clone(),
+                    round: game.round,
+                    turns_played: s.turns_played,
+                    survived: s.survived,
+                    fleet_generated: s.fleet_generated,
+                    fleet_lost: s.fleet_lost,
+                    fleet_reinforced: s.fleet_reinforced,
+                    largest_attack: s.largest_attack,
+                    largest_loss: s.largest_loss,
+                    largest_reinforcement: s.largest_reinforcement,
+                    planets_lost: s.planets_lost,
+                    planets_conquered: s.planets_conquered,
+                    planets_defended: s.planets_defended,
+                    planets_attacked: s.planets_attacked,
+                    num_fleet_lost: s.num_fleet_lost,
+                    num_fleet_reinforced: s.num_fleet_reinforced,
+                    num_fleet_generated: s.num_fleet_generated,
+                    total_troops_generated: s.total_troops_generated,
+                })
+            })
+            .collect()
+    }
+}
+
+impl From<&[SqlGamePlayerStats]> for PublicStatsSummary {
+    fn from(stats: &[SqlGamePlayerStats]) -> Self {
+        if stats.is_empty() {
+            return Self::default();
+        }
+        let count = stats.len() as f32;
+        let avg = |stat: fn(&SqlGamePlayerStats) -> i32| stats.iter().map(|s| stat(s) as f32).sum::<f32>() / count;
+
+        Self {
+            games_played: stats.iter().map(|s| &s.game_id).collect::<HashSet<&String>>().len() as i32,
+            survival_rate: stats.iter().filter(|s| s.survived).count() as f32 / count,
+            turns_played: avg(|s| s.turns_played),
+            fleet_generated: avg(|s| s.fleet_generated),
+            fleet_lost: avg(|s| s.fleet_lost),
+            fleet_reinforced: avg(|s| s.fleet_reinforced),
+            largest_attack: avg(|s| s.largest_attack),
+            largest_loss: avg(|s| s.largest_loss),
+            largest_reinforcement: avg(|s| s.largest_reinforcement),
+            planets_lost: avg(|s| s.planets_lost),
+            planets_conquered: avg(|s| s.planets_conquered),
+            planets_defended: avg(|s| s.planets_defended),
+            planets_attacked: avg(|s| s.planets_attacked),
+            num_fleet_lost: avg(|s| s.num_fleet_lost),
+            num_fleet_reinforced: avg(|s| s.num_fleet_reinforced),
+            num_fleet_generated: avg(|s| s.num_fleet_generated),
+            total_troops_generated: avg(|s| s.total_troops_generated),
+        }
+    }
+}
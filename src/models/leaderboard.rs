@@ -0,0 +1,120 @@
+use diesel::prelude::{Insertable, Queryable};
+use serde::Serialize;
+use chrono::{NaiveDateTime, Local};
+use uuid::Uuid;
+use crate::db::schema::leaderboard_snapshots::{self};
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LeaderboardEntry {
+    pub rank: usize,
+    pub team_id: String,
+    pub team_name: String,
+    pub elo: i32,
+    pub wins: i32,
+    pub losses: i32,
+    pub games_played: i32,
+    pub win_rate: f32,
+    pub survival_rate: f32,
+    pub elo_trend: i32,
+}
+
+#[derive(Debug)]
+pub struct NewLeaderboardSnapshot {
+    pub competition_id: String,
+    pub round: i32,
+    pub entry: LeaderboardEntry,
+}
+
+#[derive(Debug)]
+pub struct LeaderboardSnapshot {
+    pub id: String,
+    pub competition_id: String,
+    pub round: i32,
+    pub team_id: String,
+    pub team_rank: i32,
+    pub elo: i32,
+    pub wins: i32,
+    pub losses: i32,
+    pub games_played: i32,
+    pub created: NaiveDateTime,
+}
+
+#[derive(Queryable, Debug, Insertable)]
+#[diesel(table_name = leaderboard_snapshots)]
+pub struct SqlLeaderboardSnapshot {
+    pub id: String,
+    pub competition_id: String,
+    pub round: i32,
+    pub team_id: String,
+    pub team_rank: i32,
+    pub elo: i32,
+    pub wins: i32,
+    pub losses: i32,
+    pub games_played: i32,
+    pub created: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PublicLeaderboardSnapshot {
+    pub id: String,
+    pub competition_id: String,
+    pub round: i32,
+    pub team_id: String,
+    pub rank: i32,
+    pub elo: i32,
+    pub wins: i32,
+    pub losses: i32,
+    pub games_played: i32,
+    pub created: NaiveDateTime,
+}
+
+impl From<SqlLeaderboardSnapshot> for LeaderboardSnapshot {
+    fn from(sql_snapshot: SqlLeaderboardSnapshot) -> Self {
+        Self {
+            id: sql_snapshot.id,
+            competition_id: sql_snapshot.competition_id,
+            round: sql_snapshot.round,
+            team_id: sql_snapshot.team_id,
+            team_rank: sql_snapshot.team_rank,
+            elo: sql_snapshot.elo,
+            wins: sql_snapshot.wins,
+            losses: sql_snapshot.losses,
+            games_played: sql_snapshot.games_played,
+            created: sql_snapshot.created,
+        }
+    }
+}
+
+impl From<LeaderboardSnapshot> for PublicLeaderboardSnapshot {
+    fn from(snapshot: LeaderboardSnapshot) -> Self {
+        Self {
+            id: snapshot.id,
+            competition_id: snapshot.competition_id,
+            round: snapshot.round,
+            team_id: snapshot.team_id,
+            rank: snapshot.team_rank,
+            elo: snapshot.elo,
+            wins: snapshot.wins,
+            losses: snapshot.losses,
+            games_played: snapshot.games_played,
+            created: snapshot.created,
+        }
+    }
+}
+
+impl From<NewLeaderboardSnapshot> for SqlLeaderboardSnapshot {
+    fn from(new_snapshot: NewLeaderboardSnapshot) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            competition_id: new_snapshot.competition_id,
+            round: new_snapshot.round,
+            team_id: new_snapshot.entry.team_id,
+            team_rank: new_snapshot.entry.rank as i32,
+            elo: new_snapshot.entry.elo,
+            wins: new_snapshot.entry.wins,
+            losses: new_snapshot.entry.losses,
+            games_played: new_snapshot.entry.games_played,
+            created: Local::now().naive_utc(),
+        }
+    }
+}
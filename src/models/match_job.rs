@@ -0,0 +1,166 @@
+use diesel::prelude::{Insertable, Queryable};
+use serde::Serialize;
+use chrono::{NaiveDateTime, Local};
+use crate::db::schema::match_jobs;
+use super::game_2v2::NewGame2v2;
+
+/// A planned ladder match. The job shares its ID with the game it produces, so a job whose
+/// game was already stored can be recognized when resuming a round.
+#[derive(Debug)]
+pub struct NewMatchJob {
+    pub id: String,
+    pub competition_id: String,
+    pub round: i32,
+    pub team1_id: String,
+    pub team2_id: String,
+    pub team1bot1_id: String,
+    pub team1bot2_id: String,
+    pub team2bot1_id: String,
+    pub team2bot2_id: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchJob {
+    pub id: String,
+    pub competition_id: String,
+    pub round: i32,
+    pub team1_id: String,
+    pub team2_id: String,
+    pub team1bot1_id: String,
+    pub team1bot2_id: String,
+    pub team2bot1_id: String,
+    pub team2bot2_id: String,
+    pub status: String,
+    pub error: String,
+    pub created: NaiveDateTime,
+    pub updated: NaiveDateTime,
+    pub worker_id: String,
+}
+
+#[derive(Queryable, Debug, Insertable)]
+#[diesel(table_name = match_jobs)]
+pub struct SqlMatchJob {
+    pub id: String,
+    pub competition_id: String,
+    pub round: i32,
+    pub team1_id: String,
+    pub team2_id: String,
+    pub team1bot1_id: String,
+    pub team1bot2_id: String,
+    pub team2bot1_id: String,
+    pub team2bot2_id: String,
+    pub status: String,
+    pub error: String,
+    pub created: NaiveDateTime,
+    pub updated: NaiveDateTime,
+    pub worker_id: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PublicMatchJob {
+    pub id: String,
+    pub competition_id: String,
+    pub round: i32,
+    pub team1_id: String,
+    pub team2_id: String,
+    pub status: String,
+    pub error: String,
+    pub created: NaiveDateTime,
+    pub updated: NaiveDateTime,
+    pub worker_id: String,
+}
+
+impl From<SqlMatchJob> for MatchJob {
+    fn from(sql_job: SqlMatchJob) -> Self {
+        Self {
+            id: sql_job.id,
+            competition_id: sql_job.competition_id,
+            round: sql_job.round,
+            team1_id: sql_job.team1_id,
+            team2_id: sql_job.team2_id,
+            team1bot1_id: sql_job.team1bot1_id,
+            team1bot2_id: sql_job.team1bot2_id,
+            team2bot1_id: sql_job.team2bot1_id,
+            team2bot2_id: sql_job.team2bot2_id,
+            status: sql_job.status,
+            error: sql_job.error,
+            created: sql_job.created,
+            updated: sql_job.updated,
+            worker_id: sql_job.worker_id,
+        }
+    }
+}
+
+impl From<NewMatchJob> for SqlMatchJob {
+    fn from(new_job: NewMatchJob) -> Self {
+        let now = Local::now().naive_utc();
+        Self {
+            id: new_job.id,
+            competition_id: new_job.competition_id,
+            round: new_job.round,
+            team1_id: new_job.team1_id,
+            team2_id: new_job.team2_id,
+            team1bot1_id: new_job.team1bot1_id,
+            team1bot2_id: new_job.team1bot2_id,
+            team2bot1_id: new_job.team2bot1_id,
+            team2bot2_id: new_job.team2bot2_id,
+            status: "pending".to_string(),
+            error: "".to_string(),
+            created: now,
+            updated: now,
+            worker_id: "".to_string(),
+        }
+    }
+}
+
+impl From<MatchJob> for PublicMatchJob {
+    fn from(job: MatchJob) -> Self {
+        Self {
+            id: job.id,
+            competition_id: job.competition_id,
+            round: job.round,
+            team1_id: job.team1_id,
+            team2_id: job.team2_id,
+            status: job.status,
+            error: job.error,
+            created: job.created,
+            updated: job.updated,
+            worker_id: job.worker_id,
+        }
+    }
+}
+
+impl From<NewGame2v2> for NewMatchJob {
+    fn from(game: NewGame2v2) -> Self {
+        Self {
+            id: game.id,
+            competition_id: game.competition_id,
+            round: game.round,
+            team1_id: game.team1_id,
+            team2_id: game.team2_id,
+            team1bot1_id: game.team1bot1_id,
+            team1bot2_id: game.team1bot2_id,
+            team2bot1_id: game.team2bot1_id,
+            team2bot2_id: game.team2bot2_id,
+        }
+    }
+}
+
+impl MatchJob {
+    /// Returns whether the job will not change anymore (it is either `done` or `failed`).
+    pub fn is_settled(&self) -> bool {
+        self.status.eq("done") || self.status.eq("failed")
+    }
+
+    /// Creates the game to be played for this job.
+    pub fn to_game(&self) -> NewGame2v2 {
+        let mut game = NewGame2v2::new(
+            self.competition_id.clone(),
+            self.round,
+            [self.team1_id.clone(), self.team2_id.clone()],
+            [self.team1bot1_id.clone(), self.team1bot2_id.clone(), self.team2bot1_id.clone(), self.team2bot2_id.clone()],
+        );
+        game.id = self.id.clone();
+        game
+    }
+}
@@ -4,4 +4,10 @@ pub mod team;
 pub mod competition;
 pub mod bot;
 pub mod game_2v2;
-pub mod game_player_stats;
\ No newline at end of file
+pub mod game_player_stats;
+pub mod leaderboard;
+pub mod final_evaluation;
+pub mod bracket;
+pub mod match_job;
+pub mod worker;
+pub mod round_plan;
\ No newline at end of file
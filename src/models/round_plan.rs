@@ -0,0 +1,68 @@
+use serde::Serialize;
+
+use super::{match_job::MatchJob, team::Team};
+
+/// The matches a round would play, as previewed by a dry run.
+#[derive(Debug, Serialize)]
+pub struct RoundPlan {
+    pub competition_id: String,
+    pub round: i32,
+    /// Whether the round was already planned and the stored matches would be resumed.
+    pub resuming: bool,
+    pub matches: Vec<PlannedMatch>,
+    pub skipped_teams: Vec<SkippedTeam>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlannedMatch {
+    pub team1_id: String,
+    pub team2_id: String,
+    pub team1bot1_id: String,
+    pub team1bot2_id: String,
+    pub team2bot1_id: String,
+    pub team2bot2_id: String,
+}
+
+/// A team left out of a round, because its bots are missing or did not compile.
+#[derive(Debug, Serialize, Clone)]
+pub struct SkippedTeam {
+    pub team_id: String,
+    pub team_name: String,
+    pub reason: String,
+}
+
+impl PlannedMatch {
+    pub fn from_teams(team1: &Team, team2: &Team) -> Self {
+        Self {
+            team1_id: team1.id.clone(),
+            team2_id: team2.id.clone(),
+            team1bot1_id: team1.bot1.clone(),
+            team1bot2_id: team1.bot2.clone(),
+            team2bot1_id: team2.bot1.clone(),
+            team2bot2_id: team2.bot2.clone(),
+        }
+    }
+}
+
+impl From<MatchJob> for PlannedMatch {
+    fn from(job: MatchJob) -> Self {
+        Self {
+            team1_id: job.team1_id,
+            team2_id: job.team2_id,
+            team1bot1_id: job.team1bot1_id,
+            team1bot2_id: job.team1bot2_id,
+            team2bot1_id: job.team2bot1_id,
+            team2bot2_id: job.team2bot2_id,
+        }
+    }
+}
+
+impl SkippedTeam {
+    pub fn new(team: &Team, reason: String) -> Self {
+        Self {
+            team_id: team.id.clone(),
+            team_name: team.name.clone(),
+            reason,
+        }
+    }
+}
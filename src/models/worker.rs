@@ -0,0 +1,184 @@
+use diesel::prelude::{Insertable, Queryable};
+use serde::{Serialize, Deserialize};
+use chrono::{NaiveDateTime, Local};
+use uuid::Uuid;
+use crate::db::schema::workers;
+use super::{competition::{SandboxLimits, DEFAULT_MATCH_TIMEOUT}, game_2v2::NewGame2v2, match_job::MatchJob};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewWorker {
+    pub name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Worker {
+    pub id: String,
+    pub name: String,
+    pub last_seen: NaiveDateTime,
+    pub created: NaiveDateTime,
+}
+
+#[derive(Queryable, Debug, Insertable)]
+#[diesel(table_name = workers)]
+pub struct SqlWorker {
+    pub id: String,
+    pub name: String,
+    pub last_seen: NaiveDateTime,
+    pub created: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PublicWorker {
+    pub id: String,
+    pub name: String,
+    pub last_seen: NaiveDateTime,
+    pub created: NaiveDateTime,
+}
+
+/// A match job as handed out to a worker.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkerMatchJob {
+    pub id: String,
+    pub competition_id: String,
+    pub round: i32,
+    pub team1_id: String,
+    pub team2_id: String,
+    pub team1bot1_id: String,
+    pub team1bot2_id: String,
+    pub team2bot1_id: String,
+    pub team2bot2_id: String,
+    /// The resource limits of the competition the job is played in.
+    #[serde(default)]
+    pub sandbox_limits: SandboxLimits,
+    /// Seconds the game may take before it is killed.
+    #[serde(default = "default_match_timeout")]
+    pub match_timeout: i32,
+}
+
+/// The parsed result of a game played by a worker.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkerGameResult {
+    pub winner_id: String,
+    pub team1bot1_survived: bool,
+    pub team1bot2_survived: bool,
+    pub team2bot1_survived: bool,
+    pub team2bot2_survived: bool,
+    pub additional_data: String,
+    #[serde(default)]
+    pub leftover_processes: i32,
+    #[serde(default = "default_outcome")]
+    pub outcome: String,
+    #[serde(default)]
+    pub draw: bool,
+}
+
+/// A failed match job reported by a worker.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkerFailure {
+    pub error: String,
+}
+
+fn default_match_timeout() -> i32 {
+    DEFAULT_MATCH_TIMEOUT
+}
+
+fn default_outcome() -> String {
+    "finished".to_string()
+}
+
+impl From<SqlWorker> for Worker {
+    fn from(sql_worker: SqlWorker) -> Self {
+        Self {
+            id: sql_worker.id,
+            name: sql_worker.name,
+            last_seen: sql_worker.last_seen,
+            created: sql_worker.created,
+        }
+    }
+}
+
+impl From<Worker> for PublicWorker {
+    fn from(worker: Worker) -> Self {
+        Self {
+            id: worker.id,
+            name: worker.name,
+            last_seen: worker.last_seen,
+            created: worker.created,
+        }
+    }
+}
+
+impl From<NewWorker> for SqlWorker {
+    fn from(new_worker: NewWorker) -> Self {
+        let now = Local::now().naive_utc();
+        Self {
+            id: Uuid::new_v4().to_string(),
+            name: new_worker.name,
+            last_seen: now,
+            created: now,
+        }
+    }
+}
+
+impl From<MatchJob> for WorkerMatchJob {
+    fn from(job: MatchJob) -> Self {
+        Self {
+            id: job.id,
+            competition_id: job.competition_id,
+            round: job.round,
+            team1_id: job.team1_id,
+            team2_id: job.team2_id,
+            team1bot1_id: job.team1bot1_id,
+            team1bot2_id: job.team1bot2_id,
+            team2bot1_id: job.team2bot1_id,
+            team2bot2_id: job.team2bot2_id,
+            sandbox_limits: SandboxLimits::default(),
+            match_timeout: DEFAULT_MATCH_TIMEOUT,
+        }
+    }
+}
+
+impl WorkerMatchJob {
+    /// Creates the game to be played for this job, it shares its ID with the job.
+    pub fn to_game(&self) -> NewGame2v2 {
+        let mut game = NewGame2v2::new(
+            self.competition_id.clone(),
+            self.round,
+            [self.team1_id.clone(), self.team2_id.clone()],
+            [self.team1bot1_id.clone(), self.team1bot2_id.clone(), self.team2bot1_id.clone(), self.team2bot2_id.clone()],
+        );
+        game.id = self.id.clone();
+        game
+    }
+}
+
+impl From<&NewGame2v2> for WorkerGameResult {
+    fn from(game: &NewGame2v2) -> Self {
+        Self {
+            winner_id: game.winner_id.clone(),
+            team1bot1_survived: game.team1bot1_survived,
+            team1bot2_survived: game.team1bot2_survived,
+            team2bot1_survived: game.team2bot1_survived,
+            team2bot2_survived: game.team2bot2_survived,
+            additional_data: game.additional_data.clone(),
+            leftover_processes: game.leftover_processes,
+            outcome: game.outcome.clone(),
+            draw: game.draw,
+        }
+    }
+}
+
+impl WorkerGameResult {
+    /// Stores the result in the given game.
+    pub fn apply_to(self, game: &mut NewGame2v2) {
+        game.winner_id = self.winner_id;
+        game.team1bot1_survived = self.team1bot1_survived;
+        game.team1bot2_survived = self.team1bot2_survived;
+        game.team2bot1_survived = self.team2bot1_survived;
+        game.team2bot2_survived = self.team2bot2_survived;
+        game.additional_data = self.additional_data;
+        game.leftover_processes = self.leftover_processes;
+        game.outcome = self.outcome;
+        game.draw = self.draw;
+    }
+}
@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpResponse, get, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use crate::{
+    controllers::jwt::exchange_token_for_user,
+    db::{
+        operations_teams::get_team_by_id,
+        operations_bot::get_bots_by_team, operations_game_player_stats::get_player_stats_by_bots
+    }, models::{game_player_stats::{PublicStatsSummary, SqlGamePlayerStats}, user::Role},
+};
+
+/// Returns the average game statistics of each bot (version) of a team.
+#[get("/bots/stats/{team_id}")]
+pub async fn bots_stats(auth: BearerAuth, team_id: web::Path<String>) -> HttpResponse {
+    let requesting_user = match exchange_token_for_user(auth) {
+        Some(u) => u,
+        None => return HttpResponse::Unauthorized().finish()
+    };
+
+    let team = match get_team_by_id(team_id.into_inner()) {
+        Ok(t) => t,
+        Err(_) => return HttpResponse::BadRequest().finish(),
+    };
+
+    if
+        requesting_user.id != team.owner &&
+        requesting_user.id != team.partner &&
+        requesting_user.role != Role::Admin
+    {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    let bots = match get_bots_by_team(team.id) {
+        Ok(bots) => bots,
+        Err(_) => return HttpResponse::InternalServerError().finish()
+    };
+    let stats = match get_player_stats_by_bots(bots.iter().map(|bot| bot.id.clone()).collect()) {
+        Ok(stats) => stats,
+        Err(_) => return HttpResponse::InternalServerError().finish()
+    };
+
+    let mut by_bot: HashMap<String, Vec<SqlGamePlayerStats>> = bots
+        .into_iter()
+        .map(|bot| (bot.id, Vec::new()))
+        .collect();
+    for s in stats.into_iter() {
+        if let Some(bot_stats) = by_bot.get_mut(&s.bot_id) {
+            bot_stats.push(s);
+        }
+    }
+
+    HttpResponse::Ok().json(
+        by_bot
+            .into_iter()
+            .map(|(bot_id, bot_stats)| (bot_id, PublicStatsSummary::from(bot_stats.as_slice())))
+            .collect::<HashMap<String, PublicStatsSummary>>()
+    )
+}
\ No newline at end of file
@@ -1,10 +1,10 @@
 use std::{path::Path, fs};
 use actix_multipart::form::{tempfile::TempFile, MultipartForm, text::Text};
-use actix_web::{HttpResponse, post};
+use actix_web::{HttpResponse, post, web};
 use actix_web_httpauth::extractors::bearer::BearerAuth;
-use chrono::{Local, Timelike, Datelike};
+use chrono::{Duration, Local, Timelike, Datelike};
 use zip::ZipArchive;
-use crate::{controllers::{jwt::exchange_token_for_user, matchmaker_2v2::{compile_team_bots, compile_bot}}, models::{bot::{NewBot, PublicBot}, team::BotSelector}, db::{operations_teams::{get_team_by_id, set_team_bot}, operations_bot::{insert_bot, set_bot_error, get_bot_by_id}}};
+use crate::{controllers::{jwt::exchange_token_for_user, matchmaker_2v2::compile_bot, file_handler::{check_zip, sha256_file}}, models::{bot::{NewBot, PublicBot}, errors::MatchMakerError, team::BotSelector}, db::{operations_teams::{get_team_by_id, set_team_bot}, operations_bot::{insert_bot_within_quota, get_bot_by_id}, operations_competition::get_competition_by_id}};
 
 #[derive(MultipartForm)]
 pub struct BotUploadData {
@@ -32,15 +32,41 @@ pub async fn bot_upload(auth: BearerAuth, payload: MultipartForm<BotUploadData>)
         return HttpResponse::Forbidden().finish();
     }
 
+    // are submissions open?
+    let competition = match get_competition_by_id(team.competition_id.clone()) {
+        Ok(c) => c,
+        Err(_) => return HttpResponse::InternalServerError().finish(),
+    };
+    let now = Local::now().naive_utc();
+    if !competition.allowed_submissions {
+        return HttpResponse::Forbidden().body("Submissions are closed for this competition");
+    }
+    if now < competition.start {
+        return HttpResponse::Forbidden().body("The competition has not started yet");
+    }
+    if now > competition.end {
+        return HttpResponse::Forbidden().body("The competition has ended");
+    }
+
     // zip correctly uploaded?
     let bot_file = match bot_file_data.file {
         Some(f) => f,
         None => return HttpResponse::BadRequest().body("Can't extract zip file."),
     };
 
-    // is it a zip?
-    if ZipArchive::new(&bot_file.file).is_err() {
-        return HttpResponse::BadRequest().body("Uploaded file is not a valid ZIP file");
+    if bot_file.size > competition.max_upload_mb as usize * 1024 * 1024 {
+        return HttpResponse::PayloadTooLarge().body(format!("Bots may be at most {} MB", competition.max_upload_mb));
+    }
+
+    // is it a zip, and safe to extract?
+    let mut archive = match ZipArchive::new(&bot_file.file) {
+        Ok(a) => a,
+        Err(_) => return HttpResponse::BadRequest().body("Uploaded file is not a valid ZIP file"),
+    };
+    match check_zip(&mut archive) {
+        Ok(()) => (),
+        Err(MatchMakerError::UnsafeZip(reason)) => return HttpResponse::BadRequest().body(reason),
+        Err(_) => return HttpResponse::BadRequest().body("Uploaded file is not a valid ZIP file"),
     }
 
     
@@ -63,23 +89,34 @@ pub async fn bot_upload(auth: BearerAuth, payload: MultipartForm<BotUploadData>)
     let save_directory = Path::new("./resources/uploads")
         .join(team.competition_id.clone())
         .join(time);
-
-    if let Err(_) = fs::create_dir_all(&save_directory) {
-        return HttpResponse::InternalServerError().body("Failed to create directory");
-    }
-
     let save_path = save_directory.join(filename);
+
+    // compiled bots are cached by the hash of their source
+    let source_hash = match sha256_file(bot_file.file.path()) {
+        Ok(h) => h,
+        Err(_) => return HttpResponse::InternalServerError().body("Failed to read file"),
+    };
     
     let bot = NewBot { 
         team_id: team.id.clone(),
         source_path: save_path.to_string_lossy().to_string(), 
+        source_hash,
     };
 
-    let bot = match insert_bot(bot) {
-        Ok(b) => b,
+    // the team may not have uploaded too many bots lately, counted together with the insert
+    let bot = match insert_bot_within_quota(bot, now.naive_utc() - Duration::hours(24), competition.upload_quota as i64) {
+        Ok(Some(b)) => b,
+        Ok(None) => return HttpResponse::TooManyRequests().body(format!(
+            "Teams may upload {} bots within 24 hours, try again later",
+            competition.upload_quota
+        )),
         Err(_) => return HttpResponse::InternalServerError().finish(),
     };
 
+    if let Err(_) = fs::create_dir_all(&save_directory) {
+        return HttpResponse::InternalServerError().body("Failed to create directory");
+    }
+
     // if team's first bot, set as default bot
     if team.bot1.eq("") {
         if let Err(_) = set_team_bot(&team, BotSelector::First, bot.id.clone()) {
@@ -97,10 +134,10 @@ pub async fn bot_upload(auth: BearerAuth, payload: MultipartForm<BotUploadData>)
         return HttpResponse::InternalServerError().body("Failed to save file")
     }
 
-    // try if bot compiles
-    if let Err(e) = compile_bot(&bot) {
-        let _ = set_bot_error(bot.clone(), e.to_string());
-    }
+    // compile the bot once, rounds reuse the result (compile errors are stored on the bot);
+    // compiling blocks for up to minutes
+    let compiled = bot.clone();
+    let _ = web::block(move || compile_bot(&compiled, &competition.language)).await;
 
     // refetch the bot (fetch potential compilation errors)
     let bot = match get_bot_by_id(bot.id) {
@@ -37,7 +37,8 @@ pub async fn bots_win_rate(auth: BearerAuth, team_id: web::Path<String>) -> Http
         Err(_) => return HttpResponse::InternalServerError().finish()
     };
 
-    let mut hm: HashMap<String, (f32, f32)> = HashMap::new();
+    // win rate, survival rate and draw rate of each bot
+    let mut hm: HashMap<String, (f32, f32, f32)> = HashMap::new();
 
     for bot in bots.into_iter() {
         let games_played = match get_games_by_bot_id(bot.id.clone()) {
@@ -52,12 +53,14 @@ pub async fn bots_win_rate(auth: BearerAuth, team_id: web::Path<String>) -> Http
 
 }
 
-fn calc_win_rate(bot_id: String, games: Vec<Game2v2>) -> (f32, f32) {
+fn calc_win_rate(bot_id: String, games: Vec<Game2v2>) -> (f32, f32, f32) {
     let mut win_count = 0.;
+    let mut draw_count = 0.;
     let mut survival_count = 0.;
     let mut game_count = 0.;
 
-    for game in games.into_iter() {
+    // void games did not count, they are left out of the rates
+    for game in games.into_iter().filter(|game| !game.void) {
         let is_bot_1 = game.team1bot1_id.eq(&bot_id);
         let is_bot_2 = game.team1bot2_id.eq(&bot_id);
         let is_bot_3 = game.team2bot1_id.eq(&bot_id);
@@ -70,6 +73,8 @@ fn calc_win_rate(bot_id: String, games: Vec<Game2v2>) -> (f32, f32) {
             }
             if game.winner_id.eq(&game.team1_id) {
                 win_count += 1.;
+            } else if game.draw {
+                draw_count += 1.;
             }
         }
 
@@ -80,6 +85,8 @@ fn calc_win_rate(bot_id: String, games: Vec<Game2v2>) -> (f32, f32) {
             }
             if game.winner_id.eq(&game.team1_id) {
                 win_count += 1.;
+            } else if game.draw {
+                draw_count += 1.;
             }
         }
 
@@ -90,6 +97,8 @@ fn calc_win_rate(bot_id: String, games: Vec<Game2v2>) -> (f32, f32) {
             }
             if game.winner_id.eq(&game.team2_id) {
                 win_count += 1.;
+            } else if game.draw {
+                draw_count += 1.;
             }
         }
 
@@ -100,11 +109,14 @@ fn calc_win_rate(bot_id: String, games: Vec<Game2v2>) -> (f32, f32) {
             }
             if game.winner_id.eq(&game.team2_id) {
                 win_count += 1.;
+            } else if game.draw {
+                draw_count += 1.;
             }
         }
     }
     (
         win_count / game_count,
-        survival_count / game_count
+        survival_count / game_count,
+        draw_count / game_count
     )
 }
\ No newline at end of file
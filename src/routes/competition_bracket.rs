@@ -0,0 +1,19 @@
+use actix_web::{HttpResponse, get, web};
+use crate::db::operations_bracket::{get_latest_bracket, get_bracket_matches};
+use crate::models::bracket::{PublicBracket, PublicBracketMatch};
+
+#[get("/competition/bracket/{comp_id}")]
+pub async fn competition_bracket(comp_id: web::Path<String>) -> HttpResponse {
+    let bracket = match get_latest_bracket(comp_id.into_inner()) {
+        Ok(b) => b,
+        Err(_) => return HttpResponse::NotFound().finish(),
+    };
+
+    match get_bracket_matches(bracket.id.clone()) {
+        Ok(matches) => {
+            let matches = matches.into_iter().map(PublicBracketMatch::from).collect();
+            HttpResponse::Ok().json(PublicBracket::new(bracket, matches))
+        },
+        Err(e) => HttpResponse::InternalServerError().json(e.to_string())
+    }
+}
@@ -0,0 +1,55 @@
+use actix_web::{HttpResponse, post, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use serde::Deserialize;
+use crate::controllers::jwt::exchange_token_for_user;
+use crate::controllers::bracket::start_bracket;
+use crate::db::operations_teams::get_teams_by_competition_id;
+use crate::models::bracket::PublicBracket;
+use crate::models::errors::MatchMakerError;
+use crate::models::user::Role;
+
+const DEFAULT_BRACKET_TEAMS: usize = 8;
+const DEFAULT_BEST_OF: i32 = 3;
+
+#[derive(Debug, Deserialize)]
+pub struct BracketData {
+    pub teams: Option<usize>,
+    pub best_of: Option<i32>,
+    pub double_elimination: Option<bool>,
+}
+
+#[post("/competition/bracket/{comp_id}")]
+pub async fn competition_bracket_start(auth: BearerAuth, comp_id: web::Path<String>, body: web::Json<BracketData>) -> HttpResponse {
+    let requesting_user = match exchange_token_for_user(auth) {
+        Some(u) => u,
+        None => return HttpResponse::Unauthorized().finish()
+    };
+
+    if Role::Admin != requesting_user.role {
+        return HttpResponse::Forbidden().finish();
+    }
+
+    let best_of = body.best_of.unwrap_or(DEFAULT_BEST_OF);
+    if best_of < 1 || best_of % 2 == 0 {
+        return HttpResponse::BadRequest().body("A series has to be played over an odd number of games");
+    }
+
+    let team_count = match get_teams_by_competition_id(comp_id.clone()) {
+        Ok(teams) => teams.len(),
+        Err(e) => return HttpResponse::InternalServerError().body(e.to_string())
+    };
+    let teams = body.teams.unwrap_or(DEFAULT_BRACKET_TEAMS.min(team_count));
+    if team_count < 2 {
+        return HttpResponse::BadRequest().body("At least two teams with compiling bots are needed");
+    }
+    if teams < 2 || teams > team_count {
+        return HttpResponse::BadRequest().body(format!("A bracket needs between 2 and {} teams", team_count));
+    }
+
+    let double_elimination = body.double_elimination.unwrap_or(false);
+    match start_bracket(comp_id.into_inner(), teams, best_of, double_elimination) {
+        Ok(bracket) => HttpResponse::Ok().json(PublicBracket::new(bracket, Vec::new())),
+        Err(MatchMakerError::NotEnoughTeams) => HttpResponse::BadRequest().body("At least two teams with compiling bots are needed"),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string())
+    }
+}
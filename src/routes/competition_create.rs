@@ -1,8 +1,11 @@
 use actix_web::{HttpResponse, post, web};
 use actix_web_httpauth::extractors::bearer::BearerAuth;
 use crate::controllers::jwt::exchange_token_for_user;
+use crate::controllers::language::{language_runtime, LANGUAGES};
+use crate::controllers::pairing::pairing_strategy;
+use crate::controllers::schedule::parse_schedule;
 use crate::db::operations_competition::insert_competition;
-use crate::models::competition::{NewCompetition, PublicCompetition};
+use crate::models::competition::{NewCompetition, PairingParams, PublicCompetition, MAX_MATCH_TIMEOUT, TIMEOUT_POLICIES};
 use crate::models::user::Role;
 
 #[post("/competition")]
@@ -16,7 +19,44 @@ pub async fn competition_create(auth: BearerAuth, body: web::Json<NewCompetition
         return HttpResponse::Forbidden().finish();
     }
 
-    match insert_competition(body.into_inner()) {
+    let new_competition = body.into_inner();
+    if let Some(strategy) = &new_competition.pairing_strategy {
+        let params = new_competition.pairing_params.as_ref();
+        if pairing_strategy(strategy, params.unwrap_or(&PairingParams::default())).is_none() {
+            return HttpResponse::BadRequest().body("Unknown pairing strategy");
+        }
+    }
+    if new_competition.pairing_params.as_ref().is_some_and(|params| !params.is_valid()) {
+        return HttpResponse::BadRequest().body("The ELO window must not be negative");
+    }
+
+    if let Some(schedule) = &new_competition.schedule {
+        if parse_schedule(schedule).is_none() {
+            return HttpResponse::BadRequest().body("Invalid schedule, expected a cron expression or an interval like '@every 10m'");
+        }
+    }
+
+    if new_competition.sandbox_limits.as_ref().is_some_and(|limits| !limits.is_valid()) {
+        return HttpResponse::BadRequest().body("Invalid sandbox limits, every limit must be positive");
+    }
+
+    if new_competition.match_timeout.is_some_and(|timeout| timeout <= 0 || timeout > MAX_MATCH_TIMEOUT) {
+        return HttpResponse::BadRequest().body(format!("The match timeout must be between 1 and {} seconds", MAX_MATCH_TIMEOUT));
+    }
+
+    if new_competition.timeout_policy.as_ref().is_some_and(|policy| !TIMEOUT_POLICIES.contains(&policy.as_str())) {
+        return HttpResponse::BadRequest().body("Unknown timeout policy, expected 'score', 'draw' or 'void'");
+    }
+
+    if new_competition.language.as_ref().is_some_and(|language| !language.is_empty() && language_runtime(language).is_none()) {
+        return HttpResponse::BadRequest().body(format!("Unknown language, expected one of {}", LANGUAGES.join(", ")));
+    }
+
+    if new_competition.upload_quota.is_some_and(|quota| quota <= 0) || new_competition.max_upload_mb.is_some_and(|size| size <= 0) {
+        return HttpResponse::BadRequest().body("The upload quota and the maximal upload size must be positive");
+    }
+
+    match insert_competition(new_competition) {
         Ok(c) => HttpResponse::Ok().json(PublicCompetition::from(c)),
         Err(e) => HttpResponse::InternalServerError().json(e.to_string())
     }
@@ -0,0 +1,28 @@
+use actix_web::{HttpResponse, get, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use crate::controllers::jwt::exchange_token_for_user;
+use crate::db::operations_final_evaluation::{get_latest_final_evaluation, get_final_standings};
+use crate::models::final_evaluation::PublicFinalEvaluation;
+use crate::models::user::Role;
+
+#[get("/competition/final/{comp_id}")]
+pub async fn competition_final(auth: BearerAuth, comp_id: web::Path<String>) -> HttpResponse {
+    let requesting_user = match exchange_token_for_user(auth) {
+        Some(u) => u,
+        None => return HttpResponse::Unauthorized().finish()
+    };
+
+    if Role::Admin != requesting_user.role {
+        return HttpResponse::Forbidden().finish();
+    }
+
+    let evaluation = match get_latest_final_evaluation(comp_id.into_inner()) {
+        Ok(e) => e,
+        Err(_) => return HttpResponse::NotFound().finish(),
+    };
+
+    match get_final_standings(evaluation.id.clone()) {
+        Ok(standings) => HttpResponse::Ok().json(PublicFinalEvaluation::new(evaluation, standings)),
+        Err(e) => HttpResponse::InternalServerError().json(e.to_string())
+    }
+}
@@ -0,0 +1,38 @@
+use actix_web::{HttpResponse, post, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use serde::Deserialize;
+use crate::controllers::jwt::exchange_token_for_user;
+use crate::controllers::final_evaluation::start_final_evaluation;
+use crate::models::errors::MatchMakerError;
+use crate::models::final_evaluation::PublicFinalEvaluation;
+use crate::models::user::Role;
+
+const DEFAULT_GAMES_PER_PAIR: i32 = 4;
+
+#[derive(Debug, Deserialize)]
+pub struct FinalEvaluationData {
+    pub games_per_pair: Option<i32>,
+}
+
+#[post("/competition/final/{comp_id}")]
+pub async fn competition_final_start(auth: BearerAuth, comp_id: web::Path<String>, body: web::Json<FinalEvaluationData>) -> HttpResponse {
+    let requesting_user = match exchange_token_for_user(auth) {
+        Some(u) => u,
+        None => return HttpResponse::Unauthorized().finish()
+    };
+
+    if Role::Admin != requesting_user.role {
+        return HttpResponse::Forbidden().finish();
+    }
+
+    let games_per_pair = body.games_per_pair.unwrap_or(DEFAULT_GAMES_PER_PAIR);
+    if games_per_pair < 1 {
+        return HttpResponse::BadRequest().body("At least one game per pair has to be played");
+    }
+
+    match start_final_evaluation(comp_id.into_inner(), games_per_pair) {
+        Ok(evaluation) => HttpResponse::Ok().json(PublicFinalEvaluation::new(evaluation, Vec::new())),
+        Err(MatchMakerError::AlreadyRunning(e)) => HttpResponse::Conflict().body(e),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string())
+    }
+}
@@ -0,0 +1,32 @@
+use actix_web::{HttpResponse, get, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use crate::controllers::jwt::exchange_token_for_user;
+use crate::db::operations_competition::get_competition_by_id;
+use crate::db::operations_match_job::get_match_jobs;
+use crate::models::match_job::PublicMatchJob;
+use crate::models::user::Role;
+
+#[get("/competition/jobs/{comp_id}")]
+pub async fn competition_jobs(auth: BearerAuth, comp_id: web::Path<String>) -> HttpResponse {
+    let requesting_user = match exchange_token_for_user(auth) {
+        Some(u) => u,
+        None => return HttpResponse::Unauthorized().finish()
+    };
+
+    if Role::Admin != requesting_user.role {
+        return HttpResponse::Forbidden().finish();
+    }
+
+    let competition = match get_competition_by_id(comp_id.into_inner()) {
+        Ok(c) => c,
+        Err(_) => return HttpResponse::NotFound().finish(),
+    };
+
+    match get_match_jobs(competition.id, competition.round) {
+        Ok(jobs) => {
+            let public_jobs: Vec<PublicMatchJob> = jobs.into_iter().map(PublicMatchJob::from).collect();
+            HttpResponse::Ok().json(public_jobs)
+        },
+        Err(e) => HttpResponse::InternalServerError().json(e.to_string())
+    }
+}
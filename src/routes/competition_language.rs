@@ -0,0 +1,47 @@
+use actix_web::{HttpResponse, post, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use serde::Deserialize;
+use crate::controllers::jwt::exchange_token_for_user;
+use crate::controllers::language::{language_runtime, LANGUAGES};
+use crate::db::operations_competition::{get_competition_by_id, set_competition_language};
+use crate::models::competition::PublicCompetition;
+use crate::models::user::Role;
+
+#[derive(Debug, Deserialize)]
+pub struct ChangeLanguageData {
+    /// Empty to detect the language of every bot.
+    pub language: String,
+}
+
+/// Changes the language of a competition's bots. Compiled bots are compiled again for the new
+/// language when they are played next, bots that failed to compile stay failed.
+#[post("/competition/language/{comp_id}")]
+pub async fn competition_language(auth: BearerAuth, comp_id: web::Path<String>, body: web::Json<ChangeLanguageData>) -> HttpResponse {
+    let requesting_user = match exchange_token_for_user(auth) {
+        Some(u) => u,
+        None => return HttpResponse::Unauthorized().finish()
+    };
+
+    if Role::Admin != requesting_user.role {
+        return HttpResponse::Forbidden().finish();
+    }
+
+    let competition = match get_competition_by_id(comp_id.into_inner()) {
+        Ok(c) => c,
+        Err(_) => return HttpResponse::NotFound().finish(),
+    };
+
+    let data = body.into_inner();
+    if !data.language.is_empty() && language_runtime(&data.language).is_none() {
+        return HttpResponse::BadRequest().body(format!("Unknown language, expected one of {}", LANGUAGES.join(", ")));
+    }
+
+    if let Err(e) = set_competition_language(competition.id.clone(), data.language) {
+        return HttpResponse::InternalServerError().json(e.to_string());
+    }
+
+    match get_competition_by_id(competition.id) {
+        Ok(c) => HttpResponse::Ok().json(PublicCompetition::from(c)),
+        Err(e) => HttpResponse::InternalServerError().json(e.to_string())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,26 @@
+use actix_web::{HttpResponse, get, web};
+use serde::Deserialize;
+use crate::controllers::leaderboard::build_leaderboard;
+use crate::db::operations_competition::get_competition_by_id;
+
+const DEFAULT_TREND_ROUNDS: i32 = 5;
+
+#[derive(Debug, Deserialize)]
+pub struct LeaderboardQuery {
+    pub rounds: Option<i32>,
+}
+
+#[get("/competition/{comp_id}/leaderboard")]
+pub async fn competition_leaderboard(comp_id: web::Path<String>, query: web::Query<LeaderboardQuery>) -> HttpResponse {
+    let competition = match get_competition_by_id(comp_id.into_inner()) {
+        Ok(c) => c,
+        Err(_) => return HttpResponse::NotFound().finish(),
+    };
+
+    let trend_rounds = query.rounds.unwrap_or(DEFAULT_TREND_ROUNDS).max(0);
+
+    match build_leaderboard(&competition, trend_rounds) {
+        Ok(leaderboard) => HttpResponse::Ok().json(leaderboard),
+        Err(e) => HttpResponse::InternalServerError().json(e.to_string())
+    }
+}
@@ -0,0 +1,24 @@
+use actix_web::{HttpResponse, get, web};
+use crate::{
+    models::leaderboard::PublicLeaderboardSnapshot,
+    db::{operations_competition::get_competition_by_id, operations_leaderboard::get_snapshots_by_round},
+};
+
+#[get("/competition/{comp_id}/leaderboard/{round}")]
+pub async fn competition_leaderboard_round(path: web::Path<(String, i32)>) -> HttpResponse {
+    let (comp_id, round) = path.into_inner();
+    let competition = match get_competition_by_id(comp_id) {
+        Ok(c) => c,
+        Err(_) => return HttpResponse::NotFound().finish(),
+    };
+
+    match get_snapshots_by_round(competition.id, round) {
+        Ok(snapshots) => HttpResponse::Ok().json(
+            snapshots
+                .into_iter()
+                .map(PublicLeaderboardSnapshot::from)
+                .collect::<Vec<PublicLeaderboardSnapshot>>()
+        ),
+        Err(e) => HttpResponse::InternalServerError().json(e.to_string())
+    }
+}
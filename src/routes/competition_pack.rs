@@ -1,33 +1,30 @@
-use std::{fs::File, io::Read};
-
-use actix_web::{HttpResponse, get, web};
+use actix_files::NamedFile;
+use actix_web::{HttpRequest, HttpResponse, get, http::header::{ContentDisposition, DispositionParam, DispositionType}, web};
 use crate::db::operations_competition::get_competition_by_id;
 
+/// Downloads the game pack of a competition.
+///
+/// The pack is streamed from disk. Responses carry an ETag and Last-Modified, so clients can
+/// cache it, and byte ranges are supported to resume downloads.
 #[get("/competition/pack/{comp_id}")]
-pub async fn competition_pack(comp_id: web::Path<String>) -> HttpResponse {
+pub async fn competition_pack(req: HttpRequest, comp_id: web::Path<String>) -> HttpResponse {
     let competition = match get_competition_by_id(comp_id.into_inner()) {
         Ok(competition) =>competition,
-        Err(e) => return HttpResponse::NotFound().finish(),
+        Err(_) => return HttpResponse::NotFound().finish(),
     };
 
     let path = competition.game_pack;
-    let mut file = match File::open(&path) {
+    let file = match NamedFile::open_async(&path).await {
         Ok(file) => file,
         Err(_) => return HttpResponse::InternalServerError().finish(),
     };
 
-    // Read the file contents into a buffer
-    let mut buffer = Vec::new();
-    if file.read_to_end(&mut buffer).is_err() {
-        return HttpResponse::InternalServerError().finish();
-    }
-
     // Get the filename for use in the Content-Disposition header
     let filename = path.split("/").last().unwrap_or("download.zip");
 
-    // Return the response
-    HttpResponse::Ok()
-        .content_type("application/zip")
-        .header("Content-Disposition", format!("attachment; filename=\"{}\"", filename))
-        .body(buffer)
+    file.set_content_disposition(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(filename.to_string())],
+        })
+        .into_response(&req)
 }
\ No newline at end of file
@@ -0,0 +1,54 @@
+use actix_web::{HttpResponse, post, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use serde::Deserialize;
+use crate::controllers::jwt::exchange_token_for_user;
+use crate::controllers::pairing::pairing_strategy;
+use crate::db::operations_competition::{get_competition_by_id, set_competition_pairing};
+use crate::models::competition::{PairingParams, PublicCompetition};
+use crate::models::user::Role;
+
+#[derive(Debug, Deserialize)]
+pub struct ChangePairingData {
+    pub strategy: String,
+    pub params: Option<PairingParams>,
+}
+
+#[post("/competition/pairing/{comp_id}")]
+pub async fn competition_pairing(auth: BearerAuth, comp_id: web::Path<String>, body: web::Json<ChangePairingData>) -> HttpResponse {
+    let requesting_user = match exchange_token_for_user(auth) {
+        Some(u) => u,
+        None => return HttpResponse::Unauthorized().finish()
+    };
+
+    if Role::Admin != requesting_user.role {
+        return HttpResponse::Forbidden().finish();
+    }
+
+    let competition = match get_competition_by_id(comp_id.into_inner()) {
+        Ok(c) => c,
+        Err(_) => return HttpResponse::NotFound().finish(),
+    };
+
+    let change_pairing_data = body.into_inner();
+    let params = change_pairing_data.params.unwrap_or_default();
+    if pairing_strategy(&change_pairing_data.strategy, &params).is_none() {
+        return HttpResponse::BadRequest().body("Unknown pairing strategy");
+    }
+    if !params.is_valid() {
+        return HttpResponse::BadRequest().body("The ELO window must not be negative");
+    }
+
+    let params = match serde_json::to_string(&params) {
+        Ok(p) => p,
+        Err(e) => return HttpResponse::InternalServerError().json(e.to_string()),
+    };
+
+    if let Err(e) = set_competition_pairing(competition.id.clone(), change_pairing_data.strategy, params) {
+        return HttpResponse::InternalServerError().json(e.to_string());
+    }
+
+    match get_competition_by_id(competition.id) {
+        Ok(c) => HttpResponse::Ok().json(PublicCompetition::from(c)),
+        Err(e) => HttpResponse::InternalServerError().json(e.to_string())
+    }
+}
@@ -0,0 +1,49 @@
+use actix_web::{HttpResponse, post, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use serde::Deserialize;
+use crate::controllers::competitions::start_competition_round;
+use crate::controllers::jwt::exchange_token_for_user;
+use crate::controllers::matchmaker_2v2::preview_2v2_round;
+use crate::db::operations_competition::get_competition_by_id;
+use crate::models::user::Role;
+
+#[derive(Debug, Deserialize)]
+pub struct RoundQuery {
+    pub dry_run: Option<bool>,
+}
+
+#[post("/competition/round/{comp_id}")]
+pub async fn competition_round(auth: BearerAuth, comp_id: web::Path<String>, query: web::Query<RoundQuery>) -> HttpResponse {
+    let requesting_user = match exchange_token_for_user(auth) {
+        Some(u) => u,
+        None => return HttpResponse::Unauthorized().finish()
+    };
+
+    if Role::Admin != requesting_user.role {
+        return HttpResponse::Forbidden().finish();
+    }
+
+    let competition = match get_competition_by_id(comp_id.into_inner()) {
+        Ok(c) => c,
+        Err(_) => return HttpResponse::NotFound().finish(),
+    };
+
+    if !competition.type_.eq("2v2") {
+        return HttpResponse::BadRequest().body("Rounds can only be run for 2v2 competitions");
+    }
+
+    if query.dry_run.unwrap_or(false) {
+        // the preview compiles bots, which would block the worker
+        return match web::block(move || preview_2v2_round(&competition)).await {
+            Ok(Ok(plan)) => HttpResponse::Ok().json(plan),
+            Ok(Err(e)) => HttpResponse::InternalServerError().body(e.to_string()),
+            Err(e) => HttpResponse::InternalServerError().body(e.to_string())
+        };
+    }
+
+    if start_competition_round(competition.id) {
+        HttpResponse::Accepted().finish()
+    } else {
+        HttpResponse::Conflict().body("Other rounds are still being played")
+    }
+}
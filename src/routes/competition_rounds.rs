@@ -16,7 +16,8 @@ type RoundData = (
     i32,            // points gained/lost in the round
     String,         // bot 1 id
     String,         // bot 2 id
-    Vec<String>     // vec of ids of matches
+    Vec<String>,    // vec of ids of matches
+    i32             // number of drawn matches
 );
 
 #[get("/competition/rounds/{team_id}")]
@@ -61,9 +62,12 @@ fn construct_output(games: Vec<Game2v2>, team_id: String) -> HashMap<i32, RoundD
         
         let bots = get_my_bots(&game, &team_id);
         let game_round = game.round.clone();
-        let current_round_score = hm.entry(game_round).or_insert((0, bots.0, bots.1, Vec::new()));
+        let current_round_score = hm.entry(game_round).or_insert((0, bots.0, bots.1, Vec::new(), 0));
         // log game id
         current_round_score.3.push(game.id.clone());
+        if game.draw {
+            current_round_score.4 += 1;
+        }
         
 
         if game.team1_id == game.team2_id {
@@ -0,0 +1,43 @@
+use actix_web::{HttpResponse, post, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use crate::controllers::jwt::exchange_token_for_user;
+use crate::db::operations_competition::{get_competition_by_id, set_competition_sandbox_limits};
+use crate::models::competition::{PublicCompetition, SandboxLimits};
+use crate::models::user::Role;
+
+/// Changes the resource limits games of a competition are played with.
+/// Limits missing in the body are reset to their defaults.
+#[post("/competition/sandbox/{comp_id}")]
+pub async fn competition_sandbox(auth: BearerAuth, comp_id: web::Path<String>, body: web::Json<SandboxLimits>) -> HttpResponse {
+    let requesting_user = match exchange_token_for_user(auth) {
+        Some(u) => u,
+        None => return HttpResponse::Unauthorized().finish()
+    };
+
+    if Role::Admin != requesting_user.role {
+        return HttpResponse::Forbidden().finish();
+    }
+
+    let competition = match get_competition_by_id(comp_id.into_inner()) {
+        Ok(c) => c,
+        Err(_) => return HttpResponse::NotFound().finish(),
+    };
+
+    let limits = body.into_inner();
+    if !limits.is_valid() {
+        return HttpResponse::BadRequest().body("Invalid sandbox limits, every limit must be positive");
+    }
+
+    let limits = match serde_json::to_string(&limits) {
+        Ok(l) => l,
+        Err(e) => return HttpResponse::InternalServerError().json(e.to_string())
+    };
+    if let Err(e) = set_competition_sandbox_limits(competition.id.clone(), limits) {
+        return HttpResponse::InternalServerError().json(e.to_string());
+    }
+
+    match get_competition_by_id(competition.id) {
+        Ok(c) => HttpResponse::Ok().json(PublicCompetition::from(c)),
+        Err(e) => HttpResponse::InternalServerError().json(e.to_string())
+    }
+}
\ No newline at end of file
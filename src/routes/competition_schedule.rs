@@ -0,0 +1,44 @@
+use actix_web::{HttpResponse, post, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use serde::Deserialize;
+use crate::controllers::jwt::exchange_token_for_user;
+use crate::controllers::schedule::parse_schedule;
+use crate::db::operations_competition::{get_competition_by_id, set_competition_schedule};
+use crate::models::competition::PublicCompetition;
+use crate::models::user::Role;
+
+#[derive(Debug, Deserialize)]
+pub struct ChangeScheduleData {
+    pub schedule: String,
+}
+
+#[post("/competition/schedule/{comp_id}")]
+pub async fn competition_schedule(auth: BearerAuth, comp_id: web::Path<String>, body: web::Json<ChangeScheduleData>) -> HttpResponse {
+    let requesting_user = match exchange_token_for_user(auth) {
+        Some(u) => u,
+        None => return HttpResponse::Unauthorized().finish()
+    };
+
+    if Role::Admin != requesting_user.role {
+        return HttpResponse::Forbidden().finish();
+    }
+
+    let competition = match get_competition_by_id(comp_id.into_inner()) {
+        Ok(c) => c,
+        Err(_) => return HttpResponse::NotFound().finish(),
+    };
+
+    let schedule = body.into_inner().schedule.trim().to_string();
+    if parse_schedule(&schedule).is_none() {
+        return HttpResponse::BadRequest().body("Invalid schedule, expected a cron expression or an interval like '@every 10m'");
+    }
+
+    if let Err(e) = set_competition_schedule(competition.id.clone(), schedule) {
+        return HttpResponse::InternalServerError().json(e.to_string());
+    }
+
+    match get_competition_by_id(competition.id) {
+        Ok(c) => HttpResponse::Ok().json(PublicCompetition::from(c)),
+        Err(e) => HttpResponse::InternalServerError().json(e.to_string())
+    }
+}
@@ -0,0 +1,47 @@
+use actix_web::{HttpResponse, post, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use serde::Deserialize;
+use crate::controllers::jwt::exchange_token_for_user;
+use crate::db::operations_competition::{get_competition_by_id, set_competition_submissions};
+use crate::models::competition::PublicCompetition;
+use crate::models::user::Role;
+
+#[derive(Debug, Deserialize)]
+pub struct ChangeSubmissionsData {
+    pub allowed_submissions: bool,
+    pub upload_quota: i32,
+    pub max_upload_mb: i32,
+}
+
+/// Opens or closes the submissions of a competition, and changes the number of bots a team may
+/// upload within 24 hours and the size of an uploaded bot.
+#[post("/competition/submissions/{comp_id}")]
+pub async fn competition_submissions(auth: BearerAuth, comp_id: web::Path<String>, body: web::Json<ChangeSubmissionsData>) -> HttpResponse {
+    let requesting_user = match exchange_token_for_user(auth) {
+        Some(u) => u,
+        None => return HttpResponse::Unauthorized().finish()
+    };
+
+    if Role::Admin != requesting_user.role {
+        return HttpResponse::Forbidden().finish();
+    }
+
+    let competition = match get_competition_by_id(comp_id.into_inner()) {
+        Ok(c) => c,
+        Err(_) => return HttpResponse::NotFound().finish(),
+    };
+
+    let data = body.into_inner();
+    if data.upload_quota <= 0 || data.max_upload_mb <= 0 {
+        return HttpResponse::BadRequest().body("The upload quota and the maximal upload size must be positive");
+    }
+
+    if let Err(e) = set_competition_submissions(competition.id.clone(), data.allowed_submissions, data.upload_quota, data.max_upload_mb) {
+        return HttpResponse::InternalServerError().json(e.to_string());
+    }
+
+    match get_competition_by_id(competition.id) {
+        Ok(c) => HttpResponse::Ok().json(PublicCompetition::from(c)),
+        Err(e) => HttpResponse::InternalServerError().json(e.to_string())
+    }
+}
\ No newline at end of file
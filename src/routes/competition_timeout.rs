@@ -0,0 +1,47 @@
+use actix_web::{HttpResponse, post, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use serde::Deserialize;
+use crate::controllers::jwt::exchange_token_for_user;
+use crate::db::operations_competition::{get_competition_by_id, set_competition_timeout};
+use crate::models::competition::{PublicCompetition, MAX_MATCH_TIMEOUT, TIMEOUT_POLICIES};
+use crate::models::user::Role;
+
+#[derive(Debug, Deserialize)]
+pub struct ChangeTimeoutData {
+    pub match_timeout: i32,
+    pub timeout_policy: String,
+}
+
+#[post("/competition/timeout/{comp_id}")]
+pub async fn competition_timeout(auth: BearerAuth, comp_id: web::Path<String>, body: web::Json<ChangeTimeoutData>) -> HttpResponse {
+    let requesting_user = match exchange_token_for_user(auth) {
+        Some(u) => u,
+        None => return HttpResponse::Unauthorized().finish()
+    };
+
+    if Role::Admin != requesting_user.role {
+        return HttpResponse::Forbidden().finish();
+    }
+
+    let competition = match get_competition_by_id(comp_id.into_inner()) {
+        Ok(c) => c,
+        Err(_) => return HttpResponse::NotFound().finish(),
+    };
+
+    let data = body.into_inner();
+    if data.match_timeout <= 0 || data.match_timeout > MAX_MATCH_TIMEOUT {
+        return HttpResponse::BadRequest().body(format!("The match timeout must be between 1 and {} seconds", MAX_MATCH_TIMEOUT));
+    }
+    if !TIMEOUT_POLICIES.contains(&data.timeout_policy.as_str()) {
+        return HttpResponse::BadRequest().body("Unknown timeout policy, expected 'score', 'draw' or 'void'");
+    }
+
+    if let Err(e) = set_competition_timeout(competition.id.clone(), data.match_timeout, data.timeout_policy) {
+        return HttpResponse::InternalServerError().json(e.to_string());
+    }
+
+    match get_competition_by_id(competition.id) {
+        Ok(c) => HttpResponse::Ok().json(PublicCompetition::from(c)),
+        Err(e) => HttpResponse::InternalServerError().json(e.to_string())
+    }
+}
\ No newline at end of file
@@ -1,24 +1,25 @@
-use std::{fs::{self, File}, io::Read};
+use std::{fs::File, io::{self, Read}, pin::Pin, task::{Context, Poll}, time::SystemTime};
 
-use actix_web::{HttpResponse, get, web};
+use actix_files::HttpRange;
+use actix_web::{
+    HttpMessage, HttpRequest, HttpResponse, get, web::{self, Bytes},
+    body::{BodySize, MessageBody},
+    http::{StatusCode, header::{self, EntityTag, HttpDate, IfModifiedSince, IfNoneMatch}},
+};
 use actix_web_httpauth::extractors::bearer::BearerAuth;
-use serde::Serialize;
+use tokio::{sync::mpsc, task::spawn_blocking};
 use zip::ZipArchive;
-use crate::{
-    db::{
-        operations_game2v2::get_game_by_id, 
-        operations_teams::get_team_by_student_for_competition
-    }, 
-    controllers::jwt::exchange_token_for_user, models::user::Role
-};
+use crate::{db::operations_game2v2::get_game_by_id, controllers::jwt::authorize_game_access};
 
-#[derive(Debug, Serialize)]
-struct GameLogResponse {
-    log_file_contents: String,
-}
+/// Bytes of the log read from the zip at once.
+const CHUNK_SIZE: usize = 64 * 1024;
 
+/// Returns the output of a game as text.
+///
+/// The log is streamed out of the game's zip (see `game_log_zip` for the zip itself). Responses
+/// carry an ETag and Last-Modified of the zip, and a byte range of the log can be requested.
 #[get("/game/log/{id}")]
-pub async fn game_log(auth: Option<BearerAuth>, id: web::Path<String>) -> HttpResponse {
+pub async fn game_log(req: HttpRequest, auth: Option<BearerAuth>, id: web::Path<String>) -> HttpResponse {
     let game = match get_game_by_id(id.clone()) {
         Ok(game) => game,
         Err(_) => return HttpResponse::NotFound().finish()
@@ -26,26 +27,8 @@ pub async fn game_log(auth: Option<BearerAuth>, id: web::Path<String>) -> HttpRe
 
     
 
-    if !game.public {
-        let auth_token = match auth {
-            Some(token) => token,
-            None => return HttpResponse::Forbidden().finish(),
-        };
-        let requesting_user_option = exchange_token_for_user(auth_token);
-        let requesting_user = match requesting_user_option {
-            Some(u) => u,
-            None => return HttpResponse::Forbidden().finish(),
-        };
-
-        if requesting_user.role != Role::Admin {
-            let team = match get_team_by_student_for_competition(requesting_user, game.competition_id.clone()) {
-                Ok(t) => t,
-                Err(_) => return HttpResponse::Unauthorized().finish(),
-            };
-            if !team.id.eq(&game.team1_id) && !team.id.eq(&game.team2_id) {
-                return HttpResponse::Forbidden().finish();
-            }
-        }
+    if let Err(status) = authorize_game_access(auth, &game) {
+        return HttpResponse::new(status);
     }
 
     let log_file_path = game.log_file_path;
@@ -56,6 +39,10 @@ pub async fn game_log(auth: Option<BearerAuth>, id: web::Path<String>) -> HttpRe
         Ok(file) => file,
         Err(_) => return HttpResponse::InternalServerError().finish(),
     };
+    let modified = match file.metadata().and_then(|metadata| metadata.modified()) {
+        Ok(modified) => modified,
+        Err(_) => return HttpResponse::InternalServerError().finish(),
+    };
 
     // Create a new ZIP archive from the file
     let mut zip = match ZipArchive::new(file) {
@@ -64,20 +51,132 @@ pub async fn game_log(auth: Option<BearerAuth>, id: web::Path<String>) -> HttpRe
     };
 
     // Assuming the log file is the first file in the archive
-    let mut log_file = match zip.by_index(0) {
-        Ok(file) => file,
+    let size = match zip.by_index(0) {
+        Ok(file) => file.size(),
         Err(_) => return HttpResponse::InternalServerError().finish(),
     };
 
-    // Read the contents of the log file
-    let mut log_file_contents = String::new();
-    if let Err(_) = log_file.read_to_string(&mut log_file_contents) {
-        return HttpResponse::InternalServerError().finish();
+    let etag = log_etag(modified, size);
+    let last_modified = HttpDate::from(modified);
+    if is_not_modified(&req, &etag, modified) {
+        return HttpResponse::NotModified()
+            .insert_header(header::ETag(etag))
+            .insert_header(header::LastModified(last_modified))
+            .finish();
     }
 
-    // Return the JSON response with a 200 OK status
-    HttpResponse::Ok()
+    let mut response = HttpResponse::Ok();
+    response
         .content_type("application/text; charset=utf-8")
-        .body(log_file_contents)
+        .insert_header((header::ACCEPT_RANGES, "bytes"))
+        .insert_header(header::ETag(etag))
+        .insert_header(header::LastModified(last_modified));
+
+    // only the first range is sent, like for static files
+    let (start, length) = match req.headers().get(header::RANGE).and_then(|value| value.to_str().ok()) {
+        Some(range) => match HttpRange::parse(range, size) {
+            Ok(ranges) if !ranges.is_empty() => {
+                response
+                    .status(StatusCode::PARTIAL_CONTENT)
+                    .insert_header((
+                        header::CONTENT_RANGE,
+                        format!("bytes {}-{}/{}", ranges[0].start, ranges[0].start + ranges[0].length - 1, size),
+                    ));
+                (ranges[0].start, ranges[0].length)
+            },
+            _ => {
+                return HttpResponse::RangeNotSatisfiable()
+                    .insert_header((header::CONTENT_RANGE, format!("bytes */{}", size)))
+                    .finish()
+            },
+        },
+        None => (0, size),
+    };
+
+    response.body(stream_zipped_log(zip, start, length))
+}
+
+/// ETag of a game log, from the modification time of its zip and the size of the log.
+fn log_etag(modified: SystemTime, size: u64) -> EntityTag {
+    EntityTag::new_strong(format!("{:x}-{:x}", unix_seconds(modified), size))
+}
+
+/// Whether the client's cached log is still current, see `If-None-Match` and `If-Modified-Since`.
+fn is_not_modified(req: &HttpRequest, etag: &EntityTag, modified: SystemTime) -> bool {
+    match req.get_header::<IfNoneMatch>() {
+        Some(IfNoneMatch::Any) => return true,
+        Some(IfNoneMatch::Items(tags)) => return tags.iter().any(|tag| tag.weak_eq(etag)),
+        None => (),
+    }
+    match req.get_header::<IfModifiedSince>() {
+        // HTTP dates are in seconds
+        Some(IfModifiedSince(since)) => unix_seconds(modified) <= unix_seconds(since.into()),
+        None => false,
+    }
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
 
+/// Streams `length` bytes of the first file of a zip, starting at `start`. The file is read on
+/// the blocking pool, at most a few chunks ahead of the client.
+fn stream_zipped_log(mut zip: ZipArchive<File>, start: u64, length: u64) -> ChannelBody {
+    let (sender, receiver) = mpsc::channel(4);
+    spawn_blocking(move || {
+        let mut log_file = match zip.by_index(0) {
+            Ok(file) => file,
+            Err(e) => {
+                let _ = sender.blocking_send(Err(io::Error::other(e)));
+                return;
+            },
+        };
+        if let Err(e) = io::copy(&mut (&mut log_file).take(start), &mut io::sink()) {
+            let _ = sender.blocking_send(Err(e));
+            return;
+        }
+
+        let mut remaining = length;
+        while remaining > 0 {
+            let mut chunk = vec![0; CHUNK_SIZE.min(remaining as usize)];
+            let read = match log_file.read(&mut chunk) {
+                Ok(0) => {
+                    let _ = sender.blocking_send(Err(io::ErrorKind::UnexpectedEof.into()));
+                    return;
+                },
+                Ok(read) => read,
+                Err(e) => {
+                    let _ = sender.blocking_send(Err(e));
+                    return;
+                },
+            };
+            chunk.truncate(read);
+            remaining -= read as u64;
+            // the client went away
+            if sender.blocking_send(Ok(Bytes::from(chunk))).is_err() {
+                return;
+            }
+        }
+    });
+    ChannelBody { size: length, receiver }
+}
+
+/// A response body of a known size, whose chunks are sent through a channel.
+struct ChannelBody {
+    size: u64,
+    receiver: mpsc::Receiver<Result<Bytes, io::Error>>,
+}
+
+impl MessageBody for ChannelBody {
+    type Error = io::Error;
+
+    fn size(&self) -> BodySize {
+        BodySize::Sized(self.size)
+    }
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        self.receiver.poll_recv(cx)
+    }
 }
\ No newline at end of file
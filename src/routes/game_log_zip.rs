@@ -0,0 +1,30 @@
+use actix_files::NamedFile;
+use actix_web::{HttpRequest, HttpResponse, get, http::header::{ContentDisposition, DispositionParam, DispositionType}, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use crate::{db::operations_game2v2::get_game_by_id, controllers::jwt::authorize_game_access};
+
+/// Downloads the zipped output of a game, as it was stored.
+///
+/// The zip is streamed from disk with an ETag and Last-Modified, and byte ranges are supported.
+#[get("/game/log/{id}/zip")]
+pub async fn game_log_zip(req: HttpRequest, auth: Option<BearerAuth>, id: web::Path<String>) -> HttpResponse {
+    let game = match get_game_by_id(id.clone()) {
+        Ok(game) => game,
+        Err(_) => return HttpResponse::NotFound().finish()
+    };
+
+    if let Err(status) = authorize_game_access(auth, &game) {
+        return HttpResponse::new(status);
+    }
+
+    let file = match NamedFile::open_async(&game.log_file_path).await {
+        Ok(file) => file,
+        Err(_) => return HttpResponse::InternalServerError().finish(),
+    };
+
+    file.set_content_disposition(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!("{}.zip", game.id))],
+        })
+        .into_response(&req)
+}
\ No newline at end of file
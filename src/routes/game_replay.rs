@@ -0,0 +1,102 @@
+use actix_web::{HttpRequest, HttpResponse, get, http::header, middleware::Compress, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use serde::Serialize;
+use crate::{
+    db::operations_game2v2::get_game_by_id,
+    controllers::{jwt::authorize_game_access, file_handler::read_zipped_log, game_log::{ReplayFrame, parse_replay}},
+};
+
+#[derive(Debug, Serialize)]
+struct ReplayResponse {
+    /// `None` if the response ends at the requested last turn and the rest was not read.
+    total_turns: Option<usize>,
+    frames: Vec<ReplayFrame>,
+}
+
+/// Returns the turns of a game as JSON frames, for the replay viewer.
+///
+/// A part of the turns can be requested with a `Range: turns={first}-{last}` header (both
+/// inclusive, `{last}` may be left out), it is answered with `206 Partial Content`. The output
+/// is only read up to `{last}`, so the total number of turns is unknown then if the game goes
+/// on. Responses are compressed if the client accepts it.
+#[get("/game/replay/{id}", wrap = "Compress::default()")]
+pub async fn game_replay(req: HttpRequest, auth: Option<BearerAuth>, id: web::Path<String>) -> HttpResponse {
+    let game = match get_game_by_id(id.clone()) {
+        Ok(game) => game,
+        Err(_) => return HttpResponse::NotFound().finish()
+    };
+
+    if let Err(status) = authorize_game_access(auth, &game) {
+        return HttpResponse::new(status);
+    }
+
+    // ranges in other units (e.g. bytes) are ignored
+    let range = req.headers()
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok()?.strip_prefix("turns="))
+        .map(turn_range);
+    let last_turn = match range {
+        Some(Some((_, last))) => last,
+        _ => None,
+    };
+
+    // unzipping and parsing a whole game would block the worker, it runs on the blocking pool
+    let log_file_path = game.log_file_path.clone();
+    let parsed = web::block(move || {
+        let log = read_zipped_log(&log_file_path).map_err(|_| None)?;
+        let lines: Vec<String> = log.lines().map(String::from).collect();
+        parse_replay(&lines, last_turn).map_err(Some)
+    }).await;
+    let replay = match parsed {
+        Ok(Ok(replay)) => replay,
+        Ok(Err(Some(e))) => return HttpResponse::InternalServerError().json(e.to_string()),
+        _ => return HttpResponse::InternalServerError().finish(),
+    };
+    let mut frames = replay.frames;
+    let total_turns = (!replay.truncated).then_some(frames.len());
+    let total = total_turns.map_or("*".to_string(), |total| total.to_string());
+
+    let range = match range {
+        Some(range) => range,
+        None => {
+            return HttpResponse::Ok()
+                .insert_header((header::ACCEPT_RANGES, "turns"))
+                .json(ReplayResponse { total_turns, frames })
+        },
+    };
+    // the range is capped to the last turn of the game
+    let range = range.and_then(|(first, last)| {
+        let last = last.unwrap_or(usize::MAX).min(frames.len().checked_sub(1)?);
+        (first <= last).then_some((first, last))
+    });
+    let (first, last) = match range {
+        Some(range) => range,
+        None => {
+            return HttpResponse::RangeNotSatisfiable()
+                .insert_header((header::CONTENT_RANGE, format!("turns */{}", total)))
+                .finish()
+        },
+    };
+
+    frames.truncate(last + 1);
+    frames.drain(..first);
+    HttpResponse::PartialContent()
+        .insert_header((header::ACCEPT_RANGES, "turns"))
+        .insert_header((header::CONTENT_RANGE, format!("turns {}-{}/{}", first, last, total)))
+        .json(ReplayResponse { total_turns, frames })
+}
+
+/// Reads a `{first}-{last}` range of turns, `{last}` is `None` if it is left out. Returns `None`
+/// if the range cannot be read or ends before it starts.
+fn turn_range(range: &str) -> Option<(usize, Option<usize>)> {
+    let (first, last) = range.split_once('-')?;
+    let first: usize = first.trim().parse().ok()?;
+    let last: Option<usize> = match last.trim() {
+        "" => None,
+        last => Some(last.parse().ok()?),
+    };
+    if last.is_some_and(|last| first > last) {
+        return None;
+    }
+    Some((first, last))
+}
\ No newline at end of file
@@ -7,6 +7,20 @@ pub mod competition_attended;
 pub mod competition_rounds;
 pub mod competition_team_count;
 pub mod competition_pack;
+pub mod competition_leaderboard;
+pub mod competition_leaderboard_round;
+pub mod competition_pairing;
+pub mod competition_schedule;
+pub mod competition_sandbox;
+pub mod competition_timeout;
+pub mod competition_language;
+pub mod competition_submissions;
+pub mod competition_jobs;
+pub mod competition_round;
+pub mod competition_final;
+pub mod competition_final_start;
+pub mod competition_bracket;
+pub mod competition_bracket_start;
 pub mod team_create;
 pub mod team_join;
 pub mod team_leave;
@@ -18,12 +32,21 @@ pub mod team_bots;
 pub mod team_bot_change;
 pub mod team_rename;
 pub mod team_id;
+pub mod team_history;
+pub mod team_stats;
 pub mod bot_upload;
 pub mod user_id;
 pub mod bot_win_rates;
+pub mod bot_stats;
 pub mod game_log;
+pub mod game_log_zip;
+pub mod game_replay;
 pub mod game_toggle_public;
 pub mod game_id;
 pub mod game_get_public;
-
-pub mod matchmaking_test;
\ No newline at end of file
+pub mod worker_register;
+pub mod worker_claim;
+pub mod worker_bot;
+pub mod worker_result;
+pub mod worker_failure;
+pub mod worker_get_all;
\ No newline at end of file
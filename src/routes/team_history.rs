@@ -0,0 +1,23 @@
+use actix_web::{HttpResponse, get, web};
+use crate::{
+    models::leaderboard::PublicLeaderboardSnapshot,
+    db::{operations_teams::get_team_by_id, operations_leaderboard::get_snapshots_by_team},
+};
+
+#[get("/team/history/{team_id}")]
+pub async fn team_history(team_id: web::Path<String>) -> HttpResponse {
+    let team = match get_team_by_id(team_id.into_inner()) {
+        Ok(t) => t,
+        Err(_) => return HttpResponse::NotFound().finish(),
+    };
+
+    match get_snapshots_by_team(team.id) {
+        Ok(snapshots) => HttpResponse::Ok().json(
+            snapshots
+                .into_iter()
+                .map(PublicLeaderboardSnapshot::from)
+                .collect::<Vec<PublicLeaderboardSnapshot>>()
+        ),
+        Err(e) => HttpResponse::InternalServerError().json(e.to_string())
+    }
+}
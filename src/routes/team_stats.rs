@@ -0,0 +1,34 @@
+use actix_web::{HttpResponse, get, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use crate::{
+    controllers::jwt::exchange_token_for_user,
+    db::{operations_teams::get_team_by_id, operations_game_player_stats::get_player_stats_by_team},
+    models::{game_player_stats::PublicStatsSummary, user::Role},
+};
+
+/// Returns the average game statistics of a team's bots over all games of the team.
+#[get("/team/stats/{team_id}")]
+pub async fn team_stats(auth: BearerAuth, team_id: web::Path<String>) -> HttpResponse {
+    let requesting_user = match exchange_token_for_user(auth) {
+        Some(u) => u,
+        None => return HttpResponse::Unauthorized().finish()
+    };
+
+    let team = match get_team_by_id(team_id.into_inner()) {
+        Ok(t) => t,
+        Err(_) => return HttpResponse::BadRequest().finish(),
+    };
+
+    if
+        requesting_user.id != team.owner &&
+        requesting_user.id != team.partner &&
+        requesting_user.role != Role::Admin
+    {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match get_player_stats_by_team(team.id) {
+        Ok(stats) => HttpResponse::Ok().json(PublicStatsSummary::from(stats.as_slice())),
+        Err(_) => HttpResponse::InternalServerError().finish()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,21 @@
+use actix_web::{HttpResponse, get, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use crate::controllers::worker::{is_worker_token, compiled_bot_archive};
+use crate::models::errors::MatchMakerError;
+
+#[get("/worker/bot/{bot_id}")]
+pub async fn worker_bot(auth: BearerAuth, bot_id: web::Path<String>) -> HttpResponse {
+    if !is_worker_token(&auth) {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    // compiling and zipping the bot blocks
+    match web::block(move || compiled_bot_archive(bot_id.into_inner())).await {
+        Ok(Ok(archive)) => HttpResponse::Ok()
+            .content_type("application/zip")
+            .body(archive),
+        Ok(Err(MatchMakerError::DatabaseError(_))) => HttpResponse::NotFound().finish(),
+        Ok(Err(e)) => HttpResponse::InternalServerError().body(e.to_string()),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string())
+    }
+}
@@ -0,0 +1,24 @@
+use actix_web::{HttpResponse, post, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use crate::controllers::worker::{is_worker_token, remote_workers_enabled, claim_job};
+
+#[post("/worker/claim/{worker_id}")]
+pub async fn worker_claim(auth: BearerAuth, worker_id: web::Path<String>) -> HttpResponse {
+    if !is_worker_token(&auth) {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    // the dashboard plays the games itself, handing them out would play them twice
+    if !remote_workers_enabled() {
+        return HttpResponse::NoContent().finish();
+    }
+
+    // claiming a job queries and updates the database
+    let claimed = web::block(move || claim_job(worker_id.into_inner())).await;
+    match claimed {
+        Ok(Ok(Some(job))) => HttpResponse::Ok().json(job),
+        Ok(Ok(None)) => HttpResponse::NoContent().finish(),
+        Ok(Err(e)) => HttpResponse::InternalServerError().body(e.to_string()),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string())
+    }
+}
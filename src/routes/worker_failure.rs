@@ -0,0 +1,19 @@
+use actix_web::{HttpResponse, post, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use crate::controllers::worker::{is_worker_token, fail_job};
+use crate::models::worker::WorkerFailure;
+
+#[post("/worker/failure/{job_id}")]
+pub async fn worker_failure(auth: BearerAuth, job_id: web::Path<String>, body: web::Json<WorkerFailure>) -> HttpResponse {
+    if !is_worker_token(&auth) {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    // finalizing the round blocks
+    let failed = web::block(move || fail_job(job_id.into_inner(), body.into_inner().error)).await;
+    match failed {
+        Ok(Ok(_)) => HttpResponse::Ok().finish(),
+        Ok(Err(e)) => HttpResponse::InternalServerError().body(e.to_string()),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string())
+    }
+}
@@ -0,0 +1,26 @@
+use actix_web::{HttpResponse, get};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use crate::controllers::jwt::exchange_token_for_user;
+use crate::db::operations_worker::get_workers;
+use crate::models::user::Role;
+use crate::models::worker::PublicWorker;
+
+#[get("/worker/all")]
+pub async fn worker_get_all(auth: BearerAuth) -> HttpResponse {
+    let requesting_user = match exchange_token_for_user(auth) {
+        Some(u) => u,
+        None => return HttpResponse::Unauthorized().finish()
+    };
+
+    if Role::Admin != requesting_user.role {
+        return HttpResponse::Forbidden().finish();
+    }
+
+    match get_workers() {
+        Ok(workers) => {
+            let public_workers: Vec<PublicWorker> = workers.into_iter().map(PublicWorker::from).collect();
+            HttpResponse::Ok().json(public_workers)
+        },
+        Err(e) => HttpResponse::InternalServerError().json(e.to_string())
+    }
+}
@@ -0,0 +1,16 @@
+use actix_web::{HttpResponse, post, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use crate::controllers::worker::{is_worker_token, register_worker};
+use crate::models::worker::{NewWorker, PublicWorker};
+
+#[post("/worker/register")]
+pub async fn worker_register(auth: BearerAuth, body: web::Json<NewWorker>) -> HttpResponse {
+    if !is_worker_token(&auth) {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    match register_worker(body.into_inner().name) {
+        Ok(w) => HttpResponse::Ok().json(PublicWorker::from(w)),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string())
+    }
+}
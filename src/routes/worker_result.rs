@@ -0,0 +1,38 @@
+use actix_multipart::form::{tempfile::TempFile, MultipartForm, text::Text};
+use actix_web::{HttpResponse, post, web};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use crate::controllers::worker::{is_worker_token, submit_result};
+use crate::models::{errors::MatchMakerError, worker::WorkerGameResult};
+
+#[derive(MultipartForm)]
+pub struct WorkerResultData {
+    result: Text<String>,
+    errors: Option<Text<String>>,
+    replay: TempFile,
+}
+
+#[post("/worker/result/{job_id}")]
+pub async fn worker_result(auth: BearerAuth, job_id: web::Path<String>, payload: MultipartForm<WorkerResultData>) -> HttpResponse {
+    if !is_worker_token(&auth) {
+        return HttpResponse::Unauthorized().finish();
+    }
+    let result_data = payload.into_inner();
+
+    let result = match serde_json::from_str::<WorkerGameResult>(&result_data.result.0) {
+        Ok(r) => r,
+        Err(e) => return HttpResponse::BadRequest().body(e.to_string()),
+    };
+    let errors = result_data.errors
+        .map(|errors| errors.0.lines().map(String::from).collect())
+        .unwrap_or_default();
+
+    // storing the game and finalizing the round block
+    let replay = result_data.replay;
+    let submitted = web::block(move || submit_result(job_id.into_inner(), result, replay.file.path(), errors)).await;
+    match submitted {
+        Ok(Ok(_)) => HttpResponse::Ok().finish(),
+        Ok(Err(MatchMakerError::JobSettled(e))) => HttpResponse::Conflict().body(e),
+        Ok(Err(e)) => HttpResponse::InternalServerError().body(e.to_string()),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string())
+    }
+}